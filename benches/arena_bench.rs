@@ -56,5 +56,32 @@ fn bench_large_context(c: &mut Criterion) {
     group.finish();
 }
 
-criterion_group!(benches, bench_small_context, bench_large_context);
+fn bench_clone(c: &mut Criterion) {
+    let mut group = c.benchmark_group("clone_20_context");
+
+    let mut err = erract::Error::not_found();
+    for i in 0..20 {
+        err = err.with_context(format!("key{i}"), format!("value{i}"));
+    }
+    let shared = err.clone_shallow();
+
+    group.bench_function("erract/clone", |b| b.iter(|| black_box(&err).clone()));
+
+    group.bench_function("erract/clone_shallow", |b| {
+        b.iter(|| black_box(&err).clone_shallow())
+    });
+
+    group.bench_function("erract/clone_of_shallow", |b| {
+        b.iter(|| black_box(&shared).clone())
+    });
+
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_small_context,
+    bench_large_context,
+    bench_clone
+);
 criterion_main!(benches);
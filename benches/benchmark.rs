@@ -8,8 +8,42 @@
 //! RUST_BACKTRACE=1 cargo bench
 //! ```
 
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::sync::atomic::{AtomicUsize, Ordering};
+
 use criterion::{Criterion, black_box, criterion_group, criterion_main};
 
+/// System allocator that counts allocations, for the allocation report in GROUP 6.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Returns how many allocations `f` performs.
+fn count_allocations<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    black_box(f());
+    ALLOCATIONS.load(Ordering::Relaxed) - before
+}
+
 // ============================================================================
 // GROUP 1: Error Creation
 // ============================================================================
@@ -83,6 +117,22 @@ fn bench_context(c: &mut Criterion) {
         b.iter(|| anyhow::anyhow!("not found").context(black_box("user_id: 12345")))
     });
 
+    // --- Integer value ---
+
+    group.bench_function("erract/int_via_to_string", |b| {
+        b.iter(|| {
+            erract::Error::permanent(erract::ErrorKind::NotFound, "not found")
+                .with_context_value(black_box("user_id"), black_box(12345))
+        })
+    });
+
+    group.bench_function("erract/int_direct", |b| {
+        b.iter(|| {
+            erract::Error::permanent(erract::ErrorKind::NotFound, "not found")
+                .with_context_int(black_box("user_id"), black_box(12345))
+        })
+    });
+
     // --- Triple context ---
 
     group.bench_function("erract/triple", |b| {
@@ -234,6 +284,30 @@ fn bench_propagation(c: &mut Criterion) {
     group.finish();
 }
 
+fn bench_raise_vs_from(c: &mut Criterion) {
+    let mut group = c.benchmark_group("raise_vs_from");
+
+    // Both paths record the caller's location; this compares their cost.
+
+    group.bench_function("erract/raise", |b| {
+        b.iter(|| -> erract::Result<()> { Err(black_box(erract::Error::not_found()).raise()) })
+    });
+
+    group.bench_function("erract/from", |b| {
+        b.iter(|| -> erract::Result<()> { Err(black_box(erract::Error::not_found()))? })
+    });
+
+    group.finish();
+
+    let raise =
+        count_allocations(|| -> erract::Result<()> { Err(erract::Error::not_found().raise()) });
+    let from = count_allocations(|| -> erract::Result<()> { Err(erract::Error::not_found())? });
+    println!("\n=== raise vs From Allocations ===");
+    println!("Error::raise: {raise}");
+    println!("From via ?:   {from}");
+    assert_eq!(raise, from, "both paths build the same single frame");
+}
+
 // ============================================================================
 // GROUP 4: Error Display / Formatting
 // ============================================================================
@@ -281,6 +355,41 @@ fn bench_display(c: &mut Criterion) {
         b.iter(|| black_box(erract_error.to_machine_string()))
     });
 
+    // --- Bare error: Display vs message_only ---
+
+    let bare_error = erract::Error::permanent(erract::ErrorKind::NotFound, "user not found");
+
+    group.bench_function("erract/bare_to_string", |b| {
+        b.iter(|| black_box(black_box(&bare_error).to_string()))
+    });
+
+    group.bench_function("erract/bare_message_only", |b| {
+        b.iter(|| black_box(black_box(&bare_error).message_only()))
+    });
+
+    group.bench_function("erract/bare_to_machine_string", |b| {
+        b.iter(|| black_box(black_box(&bare_error).to_machine_string()))
+    });
+
+    group.finish();
+}
+
+fn bench_to_json_scaling(c: &mut Criterion) {
+    let mut group = c.benchmark_group("to_json_context_scaling");
+
+    for pairs in [0usize, 1, 10, 100] {
+        let mut error = erract::Error::permanent(erract::ErrorKind::NotFound, "user not found")
+            .with_operation("user_service");
+        for i in 0..pairs {
+            error = error.with_context(format!("key_{i}"), format!("value number {i}"));
+        }
+        let error = error.clone_shallow();
+
+        group.bench_function(format!("erract/{pairs}_pairs"), |b| {
+            b.iter(|| black_box(black_box(&error).to_json()))
+        });
+    }
+
     group.finish();
 }
 
@@ -375,6 +484,50 @@ fn bench_memory(c: &mut Criterion) {
         "erract::ErrorStatus:     {} bytes",
         std::mem::size_of::<erract::ErrorStatus>()
     );
+
+    report_machine_string_allocations();
+}
+
+/// Reports allocations of the machine-string paths and checks that the kind and
+/// status portions allocate nothing.
+fn report_machine_string_allocations() {
+    let bare_error = erract::Error::permanent(erract::ErrorKind::NotFound, "user not found");
+    let mut buffer = String::with_capacity(64);
+
+    let kind = count_allocations(|| bare_error.kind().to_machine_string());
+    let status = count_allocations(|| bare_error.status().to_machine_string());
+    let written = count_allocations(|| {
+        buffer.clear();
+        bare_error.kind().write_machine_string(&mut buffer)
+    });
+    let bare = count_allocations(|| bare_error.to_machine_string());
+
+    println!("\n=== Machine String Allocations ===");
+    println!("ErrorKind::to_machine_string:    {kind}");
+    println!("ErrorStatus::to_machine_string:  {status}");
+    println!("ErrorKind::write_machine_string: {written}");
+    println!("Error::to_machine_string (bare): {bare}");
+
+    assert_eq!(kind, 0, "core kind machine strings must not allocate");
+    assert_eq!(status, 0, "status machine strings must not allocate");
+    assert_eq!(written, 0, "writing a kind into a buffer must not allocate");
+    assert_eq!(bare, 1, "a bare error allocates only its output buffer");
+
+    #[cfg(feature = "http")]
+    {
+        let kind = erract::ErrorKind::Http(erract::http::HttpErrorKind::ClientError(418));
+        let owned = count_allocations(|| kind.to_machine_string());
+        let written = count_allocations(|| {
+            buffer.clear();
+            kind.write_machine_string(&mut buffer)
+        });
+        println!("http client_error to_machine_string:    {owned}");
+        println!("http client_error write_machine_string: {written}");
+        assert_eq!(
+            written, 0,
+            "writing an HTTP kind into a buffer must not allocate"
+        );
+    }
 }
 
 // ============================================================================
@@ -515,7 +668,9 @@ criterion_group!(
     bench_error_creation,
     bench_context,
     bench_propagation,
+    bench_raise_vs_from,
     bench_display,
+    bench_to_json_scaling,
     bench_inspection,
     bench_memory,
     bench_scenario,
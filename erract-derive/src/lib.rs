@@ -0,0 +1,96 @@
+//! Derive macros for erract.
+//!
+//! Use them through the `derive` feature of `erract` rather than depending on
+//! this crate directly.
+
+use proc_macro::TokenStream;
+use quote::quote;
+use syn::{Data, DeriveInput, Fields, parse_macro_input};
+
+/// Derives `erract::ErrorContext`, turning each named field into a context pair.
+///
+/// The field name is the key and its `Display` output the value. Field
+/// attributes:
+///
+/// - `#[error_context(debug)]` formats the value with `Debug` instead.
+/// - `#[error_context(skip)]` leaves the field out.
+#[proc_macro_derive(ErrorContext, attributes(error_context))]
+pub fn derive_error_context(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    expand(input)
+        .unwrap_or_else(syn::Error::into_compile_error)
+        .into()
+}
+
+fn expand(input: DeriveInput) -> syn::Result<proc_macro2::TokenStream> {
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(fields) => &fields.named,
+            _ => {
+                return Err(syn::Error::new_spanned(
+                    &input.ident,
+                    "ErrorContext can only be derived for structs with named fields",
+                ));
+            }
+        },
+        _ => {
+            return Err(syn::Error::new_spanned(
+                &input.ident,
+                "ErrorContext can only be derived for structs",
+            ));
+        }
+    };
+
+    let mut pairs = Vec::with_capacity(fields.len());
+    for field in fields {
+        let (mut skip, mut debug) = (false, false);
+        for attr in field
+            .attrs
+            .iter()
+            .filter(|a| a.path().is_ident("error_context"))
+        {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("skip") {
+                    skip = true;
+                    Ok(())
+                } else if meta.path.is_ident("debug") {
+                    debug = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("expected `skip` or `debug`"))
+                }
+            })?;
+        }
+        if skip {
+            continue;
+        }
+        let ident = field.ident.as_ref().expect("named field");
+        let key = ident.to_string();
+        let value = if debug {
+            quote!(::std::format!("{:?}", self.#ident))
+        } else {
+            quote!(::std::string::ToString::to_string(&self.#ident))
+        };
+        pairs.push(quote! {
+            (
+                ::std::borrow::Cow::Borrowed(#key),
+                ::std::borrow::Cow::Owned(#value),
+            )
+        });
+    }
+
+    let name = &input.ident;
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+    Ok(quote! {
+        impl #impl_generics ::erract::ErrorContext for #name #ty_generics #where_clause {
+            fn error_context(
+                &self,
+            ) -> ::std::vec::Vec<(
+                ::std::borrow::Cow<'static, str>,
+                ::std::borrow::Cow<'static, str>,
+            )> {
+                ::std::vec![#(#pairs),*]
+            }
+        }
+    })
+}
@@ -3,6 +3,7 @@
 use smallvec::SmallVec;
 use std::borrow::Cow;
 use std::cell::RefCell;
+use std::sync::Arc;
 use std::sync::atomic::{AtomicUsize, Ordering};
 
 /// Type alias for context storage.
@@ -30,9 +31,13 @@ pub enum ContextHandle {
         len: usize,
         /// Thread ID to ensure safety.
         thread_id: usize,
+        /// Arena generation at commit time; reads fail once the arena is cleared.
+        generation: u32,
     },
     /// Context has been promoted to the heap (e.g., after cross-thread move or for large maps).
     Heap(Box<ContextVec>),
+    /// Context is shared between clones by reference counting.
+    Shared(Arc<[(Cow<'static, str>, Cow<'static, str>)]>),
     /// No context attached.
     #[default]
     Empty,
@@ -45,21 +50,7 @@ impl serde::Serialize for ContextHandle {
         S: serde::Serializer,
     {
         use serde::ser::SerializeSeq;
-        let pairs = match self {
-            Self::Empty => Vec::new(),
-            Self::Heap(v) => v.to_vec(),
-            Self::Arena {
-                offset,
-                len,
-                thread_id,
-            } => {
-                if *thread_id == current_thread_id() {
-                    with_arena(|arena| arena.get_pairs(*offset, *len))
-                } else {
-                    Vec::new()
-                }
-            }
-        };
+        let pairs = self.to_vec();
         let mut seq = serializer.serialize_seq(Some(pairs.len()))?;
         for pair in pairs {
             seq.serialize_element(&pair)?;
@@ -90,10 +81,27 @@ impl<'de> serde::Deserialize<'de> for ContextHandle {
 
 impl ContextHandle {
     /// Returns the number of context items.
+    ///
+    /// Like [`to_vec`](Self::to_vec), arena handles count as empty from other
+    /// threads and after the arena has been cleared.
     pub fn len(&self) -> usize {
         match self {
-            Self::Arena { len, .. } => *len,
+            Self::Arena {
+                len,
+                thread_id,
+                generation,
+                ..
+            } => {
+                if *thread_id == current_thread_id()
+                    && with_arena(|arena| arena.generation()) == *generation
+                {
+                    *len
+                } else {
+                    0
+                }
+            }
             Self::Heap(v) => v.len(),
+            Self::Shared(v) => v.len(),
             Self::Empty => 0,
         }
     }
@@ -102,23 +110,96 @@ impl ContextHandle {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Returns the heap bytes this context holds.
+    ///
+    /// Arena handles report the bytes they occupy in the thread-local arena.
+    pub fn heap_size(&self) -> usize {
+        const PAIR: usize = std::mem::size_of::<(Cow<'static, str>, Cow<'static, str>)>();
+        match self {
+            Self::Empty => 0,
+            Self::Heap(v) => {
+                let spilled = if v.spilled() { v.capacity() * PAIR } else { 0 };
+                std::mem::size_of::<ContextVec>() + spilled + owned_bytes(v)
+            }
+            Self::Shared(v) => 2 * std::mem::size_of::<usize>() + v.len() * PAIR + owned_bytes(v),
+            Self::Arena { .. } => self
+                .to_vec()
+                .iter()
+                .map(|(k, v)| encoded_pair_len(k, v))
+                .sum(),
+        }
+    }
+
+    /// Copies the context pairs out of their storage.
+    ///
+    /// Arena handles read as empty from other threads and after the arena
+    /// has been cleared.
+    pub fn to_vec(&self) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+        match self {
+            Self::Empty => Vec::new(),
+            Self::Heap(v) => v.to_vec(),
+            Self::Shared(v) => v.to_vec(),
+            Self::Arena {
+                offset,
+                len,
+                thread_id,
+                generation,
+            } => {
+                if *thread_id == current_thread_id() {
+                    with_arena(|arena| arena.get_pairs(*offset, *len, *generation))
+                } else {
+                    Vec::new()
+                }
+            }
+        }
+    }
+}
+
+/// Sums the heap capacity of the owned strings in `pairs`.
+fn owned_bytes(pairs: &[(Cow<'static, str>, Cow<'static, str>)]) -> usize {
+    let owned = |s: &Cow<'static, str>| match s {
+        Cow::Owned(s) => s.capacity(),
+        Cow::Borrowed(_) => 0,
+    };
+    pairs.iter().map(|(k, v)| owned(k) + owned(v)).sum()
+}
+
+/// Bytes of the length prefix written before each key and value in the arena.
+const LEN_PREFIX: usize = std::mem::size_of::<u32>();
+
+/// Returns the bytes one pair occupies in the arena: two length prefixes plus
+/// the key and value bytes.
+fn encoded_pair_len(key: &str, value: &str) -> usize {
+    2 * LEN_PREFIX + key.len() + value.len()
 }
 
 /// A thread-local arena for storing error context.
 pub struct ContextArena {
     buffer: Vec<u8>,
+    generation: u32,
 }
 
 impl ContextArena {
     fn new() -> Self {
         Self {
             buffer: Vec::with_capacity(8192),
+            generation: 0,
         }
     }
 
     /// Clears the arena. Should be called periodically to avoid memory growth.
+    ///
+    /// Bumps the generation so handles committed before the clear read as empty.
     pub fn clear(&mut self) {
         self.buffer.clear();
+        self.generation = self.generation.wrapping_add(1);
+    }
+
+    /// Returns the current generation, bumped on every [`clear`](Self::clear).
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        self.generation
     }
 
     /// Allocates space for context pairs in the arena.
@@ -128,6 +209,8 @@ impl ContextArena {
         pairs: &[(Cow<'static, str>, Cow<'static, str>)],
     ) -> (usize, usize) {
         let offset = self.buffer.len();
+        self.buffer
+            .reserve(pairs.iter().map(|(k, v)| encoded_pair_len(k, v)).sum());
         for (k, v) in pairs {
             let k_bytes = k.as_bytes();
             let v_bytes = v.as_bytes();
@@ -142,30 +225,38 @@ impl ContextArena {
     }
 
     /// Retrieves pairs from the arena at a given offset.
+    ///
+    /// Returns no pairs if `generation` is stale.
     pub fn get_pairs(
         &self,
         offset: usize,
         len: usize,
+        generation: u32,
     ) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+        if generation != self.generation {
+            return Vec::new();
+        }
         let mut pairs = Vec::with_capacity(len);
         let mut pos = offset;
         for _ in 0..len {
-            if pos + 4 > self.buffer.len() {
+            if pos + LEN_PREFIX > self.buffer.len() {
                 break;
             }
-            let k_len = u32::from_le_bytes(self.buffer[pos..pos + 4].try_into().unwrap()) as usize;
-            pos += 4;
+            let k_len =
+                u32::from_le_bytes(self.buffer[pos..pos + LEN_PREFIX].try_into().unwrap()) as usize;
+            pos += LEN_PREFIX;
             if pos + k_len > self.buffer.len() {
                 break;
             }
             let k = String::from_utf8_lossy(&self.buffer[pos..pos + k_len]).into_owned();
             pos += k_len;
 
-            if pos + 4 > self.buffer.len() {
+            if pos + LEN_PREFIX > self.buffer.len() {
                 break;
             }
-            let v_len = u32::from_le_bytes(self.buffer[pos..pos + 4].try_into().unwrap()) as usize;
-            pos += 4;
+            let v_len =
+                u32::from_le_bytes(self.buffer[pos..pos + LEN_PREFIX].try_into().unwrap()) as usize;
+            pos += LEN_PREFIX;
             if pos + v_len > self.buffer.len() {
                 break;
             }
@@ -186,11 +277,15 @@ pub fn with_arena<R>(f: impl FnOnce(&mut ContextArena) -> R) -> R {
 /// Commits the current dynamic context to the arena.
 pub fn commit_to_arena(pairs: &[(Cow<'static, str>, Cow<'static, str>)]) -> ContextHandle {
     let thread_id = current_thread_id();
-    let (offset, len) = with_arena(|arena| arena.push_pairs(pairs));
+    let (offset, len, generation) = with_arena(|arena| {
+        let (offset, len) = arena.push_pairs(pairs);
+        (offset, len, arena.generation)
+    });
     ContextHandle::Arena {
         offset,
         len,
         thread_id,
+        generation,
     }
 }
 
@@ -221,6 +316,19 @@ mod tests {
         assert!(size <= 32, "ContextHandle size {size} exceeds 32 bytes");
     }
 
+    #[test]
+    fn test_shared_context_survives_thread_move() {
+        let error = Error::not_found()
+            .with_context("a", "1")
+            .with_context("b", "2")
+            .clone_shallow();
+
+        let copy = error.clone();
+        let pairs = std::thread::spawn(move || copy.context()).join().unwrap();
+        assert_eq!(pairs.len(), 2);
+        assert!(matches!(error.context, ContextHandle::Shared(_)));
+    }
+
     #[test]
     fn test_arena_push_get() {
         let pairs = vec![
@@ -230,7 +338,7 @@ mod tests {
 
         with_arena(|arena| {
             let (offset, len) = arena.push_pairs(&pairs);
-            let retrieved = arena.get_pairs(offset, len);
+            let retrieved = arena.get_pairs(offset, len, arena.generation());
             assert_eq!(retrieved.len(), 2);
             assert_eq!(retrieved[0].0, "key1");
             assert_eq!(retrieved[1].1, "val2");
@@ -246,4 +354,40 @@ mod tests {
             assert_eq!(arena.buffer.len(), 0);
         });
     }
+
+    #[test]
+    fn test_heap_size_matches_encoding() {
+        let pairs = [
+            ("user_id".into(), "42".into()),
+            ("region".into(), "eu-west".into()),
+        ];
+        let before = with_arena(|arena| arena.buffer.len());
+        let handle = commit_to_arena(&pairs);
+        let written = with_arena(|arena| arena.buffer.len()) - before;
+        assert_eq!(handle.heap_size(), written);
+    }
+
+    #[test]
+    fn test_stale_handle_after_clear() {
+        let error = Error::not_found()
+            .with_context("a", "1")
+            .with_context("b", "2");
+        assert!(matches!(error.context, ContextHandle::Arena { .. }));
+        assert_eq!(error.context().len(), 2);
+        assert_eq!(error.context.len(), 2);
+
+        let handle = commit_to_arena(&[("c".into(), "3".into())]);
+        let len = std::thread::spawn(move || handle.len()).join().unwrap();
+        assert_eq!(len, 0);
+
+        with_arena(|arena| arena.clear());
+        // Overwrite the old offsets with different data.
+        let _other = Error::not_found()
+            .with_context("x", "garbage")
+            .with_context("y", "garbage");
+
+        assert!(error.context().is_empty());
+        assert!(error.context.is_empty());
+        assert!(error.to_json().ends_with(r#""message":"not found"}"#));
+    }
 }
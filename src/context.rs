@@ -2,6 +2,34 @@ use std::borrow::Cow;
 
 use crate::Error;
 
+/// Types whose fields can be attached to an error as context in one call.
+///
+/// Usually derived with `#[derive(ErrorContext)]` (the `derive` feature), which
+/// uses field names as keys and `Display` output as values. Attach the pairs
+/// with [`Error::with_context_from`].
+///
+/// # Examples
+///
+/// ```
+/// use std::borrow::Cow;
+/// use erract::{Error, ErrorContext};
+///
+/// struct Shard(u32);
+///
+/// impl ErrorContext for Shard {
+///     fn error_context(&self) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+///         vec![("shard".into(), self.0.to_string().into())]
+///     }
+/// }
+///
+/// let error = Error::timeout().with_context_from(&Shard(3));
+/// assert_eq!(error.context()[0].1, "3");
+/// ```
+pub trait ErrorContext {
+    /// Returns the context pairs describing this value.
+    fn error_context(&self) -> Vec<(Cow<'static, str>, Cow<'static, str>)>;
+}
+
 /// Trait for adding context to errors.
 ///
 /// This trait provides ergonomic methods for adding key-value context
@@ -82,6 +110,26 @@ impl<T> AddContextIter for Result<T, Error> {
     }
 }
 
+impl<T> AddContextIter for Result<T, exn::Exn<Error>> {
+    /// Raises a [context frame](Error::is_context_frame) carrying only the given
+    /// pairs; the kind and message stay with the wrapped error.
+    #[track_caller]
+    fn with_context_iter<K, V>(
+        self,
+        iter: impl IntoIterator<Item = (K, V)>,
+    ) -> Result<T, exn::Exn<Error>>
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        let frame = match &self {
+            Ok(_) => return self,
+            Err(exn) => Error::context_frame(exn.as_error()),
+        };
+        exn::ResultExt::or_raise(self, || frame.with_context_iter(iter))
+    }
+}
+
 impl AddContextIter for Error {
     #[inline]
     fn with_context_iter<K, V>(mut self, iter: impl IntoIterator<Item = (K, V)>) -> Error
@@ -96,6 +144,108 @@ impl AddContextIter for Error {
     }
 }
 
+/// Wraps the error in a new frame built by `make_error`, attaching `pairs` to
+/// that new frame rather than to the inner one.
+///
+/// Lets each layer record its own identifiers on its own frame.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, ErrorKind, context::or_raise_with_context};
+///
+/// let inner: Result<(), Error> = Err(Error::not_found().with_context("user_id", "42"));
+/// let exn = or_raise_with_context(
+///     inner,
+///     || Error::permanent(ErrorKind::Unexpected, "load profile"),
+///     [("tenant", "acme")],
+/// )
+/// .unwrap_err();
+/// assert_eq!(exn.as_error().context()[0].0, "tenant");
+/// ```
+#[track_caller]
+pub fn or_raise_with_context<R, K, V>(
+    result: R,
+    make_error: impl FnOnce() -> Error,
+    pairs: impl IntoIterator<Item = (K, V)>,
+) -> exn::Result<R::Success, Error>
+where
+    R: exn::ResultExt,
+    K: Into<Cow<'static, str>>,
+    V: Into<Cow<'static, str>>,
+{
+    result.or_raise(|| make_error().with_context_iter(pairs))
+}
+
+#[cfg(feature = "ambient")]
+tokio::task_local! {
+    /// The current request's ID, for [`Error::inherit_request_id`].
+    ///
+    /// Set it in middleware with `REQUEST_ID.scope(id, future)`, or
+    /// `REQUEST_ID.sync_scope(id, f)` in synchronous code.
+    pub static REQUEST_ID: Cow<'static, str>;
+}
+
+#[cfg(feature = "ambient")]
+thread_local! {
+    static SCOPE_STACK: std::cell::RefCell<Vec<(&'static str, &'static str)>> =
+        const { std::cell::RefCell::new(Vec::new()) };
+}
+
+/// Guard returned by [`scope`]; removes its pairs from the ambient context on drop.
+#[cfg(feature = "ambient")]
+#[must_use = "the scope ends as soon as the guard is dropped"]
+#[derive(Debug)]
+pub struct ContextScope {
+    depth: usize,
+    // Scopes live on a thread-local stack, so the guard must not change threads.
+    _not_send: std::marker::PhantomData<*const ()>,
+}
+
+/// Attaches `pairs` to every [`Error`] created on this thread until the guard drops.
+///
+/// Nested scopes accumulate, outermost pairs first.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, context};
+///
+/// let _request = context::scope(&[("request_id", "r-42")]);
+/// let error = Error::not_found();
+/// assert_eq!(error.context()[0].1, "r-42");
+/// ```
+#[cfg(feature = "ambient")]
+pub fn scope(pairs: &[(&'static str, &'static str)]) -> ContextScope {
+    let depth = SCOPE_STACK.with(|stack| {
+        let mut stack = stack.borrow_mut();
+        let depth = stack.len();
+        stack.extend_from_slice(pairs);
+        depth
+    });
+    ContextScope {
+        depth,
+        _not_send: std::marker::PhantomData,
+    }
+}
+
+#[cfg(feature = "ambient")]
+impl Drop for ContextScope {
+    fn drop(&mut self) {
+        // Truncating to the recorded depth also discards pairs of inner scopes that
+        // were leaked with `mem::forget`, so the stack stays consistent.
+        let _ = SCOPE_STACK.try_with(|stack| stack.borrow_mut().truncate(self.depth));
+    }
+}
+
+/// Returns the pairs of all active scopes on this thread, outermost first.
+#[cfg(feature = "ambient")]
+pub(crate) fn ambient_pairs() -> Vec<(&'static str, &'static str)> {
+    SCOPE_STACK
+        .try_with(|stack| stack.borrow().clone())
+        .unwrap_or_default()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -148,6 +298,77 @@ mod tests {
         assert!(result.is_ok());
     }
 
+    #[test]
+    fn test_with_context_iter_on_exn() {
+        let result: crate::Result<()> = Err(Error::not_found().with_context("a", "1").raise());
+        let exn = result.with_context_iter([("b", "2")]).unwrap_err();
+
+        assert_eq!(crate::count_frames(&exn), 2);
+        assert_eq!(crate::count_errors(&exn), 1);
+        assert!(exn.as_error().is_context_frame());
+        assert_eq!(exn.as_error().kind(), &crate::ErrorKind::NotFound);
+        assert_eq!(exn.as_error().message(), "");
+        assert_eq!(exn.as_error().context().len(), 1);
+        assert!(!crate::has_retryable(&exn));
+        assert_eq!(
+            crate::extract::display_chain(&exn),
+            "[b: 2] not found [a: 1]"
+        );
+    }
+
+    #[test]
+    fn test_or_raise_with_context_per_frame() {
+        let inner: crate::Result<()> =
+            Err(Error::not_found().with_context("user_id", "42").raise());
+        let exn = or_raise_with_context(
+            inner,
+            || Error::permanent(crate::ErrorKind::Unexpected, "load profile"),
+            [("tenant", "acme")],
+        )
+        .unwrap_err();
+
+        assert_eq!(crate::count_frames(&exn), 2);
+        let frames = crate::extract::causes_outer_first(&exn);
+        assert_eq!(frames[0].context(), vec![("tenant".into(), "acme".into())]);
+        assert_eq!(frames[1].context(), vec![("user_id".into(), "42".into())]);
+    }
+
+    #[cfg(feature = "ambient")]
+    #[test]
+    fn test_inherit_request_id() {
+        let error =
+            REQUEST_ID.sync_scope("req-7".into(), || Error::not_found().inherit_request_id());
+        assert_eq!(error.context(), vec![("request_id".into(), "req-7".into())]);
+
+        let error = REQUEST_ID.sync_scope("req-8".into(), || {
+            Error::not_found()
+                .with_context("request_id", "set-by-caller")
+                .inherit_request_id()
+        });
+        assert_eq!(error.context()[0].1, "set-by-caller");
+
+        assert!(Error::not_found().inherit_request_id().context().is_empty());
+    }
+
+    #[cfg(feature = "ambient")]
+    #[tokio::test]
+    async fn test_inherit_request_id_in_task() {
+        async fn handler() -> Error {
+            tokio::task::yield_now().await;
+            Error::not_found().inherit_request_id()
+        }
+
+        let error = REQUEST_ID.scope("req-9".into(), handler()).await;
+        assert_eq!(error.context(), vec![("request_id".into(), "req-9".into())]);
+
+        // Task-locals do not follow spawned tasks, so errors there skip the ID.
+        let spawned = REQUEST_ID
+            .scope("req-10".into(), async { tokio::spawn(handler()).await })
+            .await
+            .unwrap();
+        assert!(spawned.context().is_empty());
+    }
+
     #[test]
     fn test_with_context_iter() {
         let result: Result<(), Error> =
@@ -165,4 +386,41 @@ mod tests {
             panic!("expected error");
         }
     }
+
+    #[cfg(feature = "ambient")]
+    #[test]
+    fn test_scope_nesting_and_cleanup() {
+        let outer = scope(&[("request_id", "r-1")]);
+        {
+            let _inner = scope(&[("user_id", "u-7"), ("tenant", "acme")]);
+            let err = Error::not_found();
+            assert_eq!(
+                err.context(),
+                vec![
+                    (Cow::Borrowed("request_id"), Cow::Borrowed("r-1")),
+                    (Cow::Borrowed("user_id"), Cow::Borrowed("u-7")),
+                    (Cow::Borrowed("tenant"), Cow::Borrowed("acme")),
+                ]
+            );
+        }
+        let err = Error::not_found().with_context("k", "v");
+        assert_eq!(err.context().len(), 2);
+        assert_eq!(err.context()[0].0, "request_id");
+
+        let unwound = std::panic::catch_unwind(|| {
+            let _inner = scope(&[("phase", "commit")]);
+            panic!("boom");
+        });
+        assert!(unwound.is_err());
+        assert_eq!(Error::not_found().context().len(), 1);
+
+        let exn = Err::<(), _>(Error::not_found().raise())
+            .with_context_iter([("retry", "1")])
+            .unwrap_err();
+        assert_eq!(exn.as_error().context().len(), 1);
+        assert!(exn.as_error().sanitize_for_client().context().is_empty());
+
+        drop(outer);
+        assert!(Error::not_found().context().is_empty());
+    }
 }
@@ -1,3 +1,5 @@
+use std::borrow::Cow;
+use std::fmt;
 use std::io;
 
 use crate::{Error, ErrorKind, ErrorStatus};
@@ -76,6 +78,51 @@ impl From<std::num::ParseFloatError> for Error {
     }
 }
 
+impl From<std::time::TryFromFloatSecsError> for Error {
+    #[inline]
+    fn from(err: std::time::TryFromFloatSecsError) -> Self {
+        Error::new(
+            ErrorKind::Validation,
+            ErrorStatus::Permanent,
+            format!("Duration conversion error: {err}"),
+        )
+    }
+}
+
+#[cfg(feature = "humantime")]
+impl From<humantime::DurationError> for Error {
+    #[inline]
+    fn from(err: humantime::DurationError) -> Self {
+        Error::new(
+            ErrorKind::Validation,
+            ErrorStatus::Permanent,
+            format!("Duration parsing error: {err}"),
+        )
+    }
+}
+
+impl From<std::num::TryFromIntError> for Error {
+    #[inline]
+    fn from(err: std::num::TryFromIntError) -> Self {
+        Error::new(
+            ErrorKind::Validation,
+            ErrorStatus::Permanent,
+            format!("Integer conversion error: {err}"),
+        )
+    }
+}
+
+impl From<std::char::CharTryFromError> for Error {
+    #[inline]
+    fn from(err: std::char::CharTryFromError) -> Self {
+        Error::new(
+            ErrorKind::Validation,
+            ErrorStatus::Permanent,
+            format!("Char conversion error: {err}"),
+        )
+    }
+}
+
 impl From<std::fmt::Error> for Error {
     #[inline]
     fn from(err: std::fmt::Error) -> Self {
@@ -112,11 +159,7 @@ impl From<std::net::AddrParseError> for Error {
 impl<T> From<std::sync::PoisonError<T>> for Error {
     #[inline]
     fn from(err: std::sync::PoisonError<T>) -> Self {
-        Error::new(
-            ErrorKind::Unexpected,
-            ErrorStatus::Permanent,
-            format!("Mutex poisoned: {err}"),
-        )
+        Error::fatal(ErrorKind::Unexpected, format!("Mutex poisoned: {err}"))
     }
 }
 
@@ -142,6 +185,121 @@ impl From<std::ffi::OsString> for Error {
     }
 }
 
+impl<T> From<std::sync::mpsc::SendError<T>> for Error {
+    #[inline]
+    fn from(err: std::sync::mpsc::SendError<T>) -> Self {
+        Error::new(
+            ErrorKind::Unexpected,
+            ErrorStatus::Permanent,
+            format!("Channel closed: {err}"),
+        )
+    }
+}
+
+impl From<std::sync::mpsc::RecvError> for Error {
+    #[inline]
+    fn from(err: std::sync::mpsc::RecvError) -> Self {
+        Error::new(
+            ErrorKind::Unexpected,
+            ErrorStatus::Permanent,
+            format!("Channel closed: {err}"),
+        )
+    }
+}
+
+impl From<std::sync::mpsc::TryRecvError> for Error {
+    #[inline]
+    fn from(err: std::sync::mpsc::TryRecvError) -> Self {
+        let status = match err {
+            std::sync::mpsc::TryRecvError::Empty => ErrorStatus::Temporary,
+            std::sync::mpsc::TryRecvError::Disconnected => ErrorStatus::Permanent,
+        };
+        Error::new(
+            ErrorKind::Unexpected,
+            status,
+            format!("Channel receive error: {err}"),
+        )
+    }
+}
+
+impl From<std::sync::mpsc::RecvTimeoutError> for Error {
+    #[inline]
+    fn from(err: std::sync::mpsc::RecvTimeoutError) -> Self {
+        let (kind, status) = match err {
+            std::sync::mpsc::RecvTimeoutError::Timeout => {
+                (ErrorKind::Timeout, ErrorStatus::Temporary)
+            }
+            std::sync::mpsc::RecvTimeoutError::Disconnected => {
+                (ErrorKind::Unexpected, ErrorStatus::Permanent)
+            }
+        };
+        Error::new(kind, status, format!("Channel receive error: {err}"))
+    }
+}
+
+/// Extension trait converting a foreign error into a kinded [`Error`] while
+/// keeping the original's text.
+///
+/// # Examples
+///
+/// ```
+/// use erract::prelude::*;
+///
+/// let parsed = "x1".parse::<u32>().map_err_to(ErrorKind::Validation, ErrorStatus::Permanent, "bad port");
+/// let error = parsed.unwrap_err();
+/// assert_eq!(error.to_string(), "bad port [cause: invalid digit found in string]");
+/// ```
+pub trait MapErrToExt<T> {
+    /// Replaces the error with a new one of the given kind, status and message,
+    /// attaching the original error's `Display` output as `cause` context.
+    fn map_err_to(
+        self,
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Result<T, Error>;
+}
+
+impl<T, E: fmt::Display> MapErrToExt<T> for Result<T, E> {
+    #[inline]
+    fn map_err_to(
+        self,
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Result<T, Error> {
+        self.map_err(|err| Error::new(kind, status, message).with_context("cause", err.to_string()))
+    }
+}
+
+/// Parses `input` as `T`, returning a permanent `Validation` error on failure.
+///
+/// The error carries the `field` name, the rejected `value` and the parser's
+/// `cause` as context.
+///
+/// # Examples
+///
+/// ```
+/// use erract::convert::parse_context;
+///
+/// assert_eq!(parse_context::<u32>("42", "age").unwrap(), 42);
+///
+/// let error = parse_context::<u32>("-1", "age").unwrap_err();
+/// assert_eq!(error.to_string(), "invalid age [field: age, value: -1, cause: invalid digit found in string]");
+/// ```
+pub fn parse_context<T>(input: &str, field: &'static str) -> Result<T, Error>
+where
+    T: std::str::FromStr,
+    T::Err: fmt::Display,
+{
+    input.parse().map_err(|err: T::Err| {
+        Error::permanent(ErrorKind::Validation, format!("invalid {field}"))
+            .with_context("field", field)
+            .with_context("value", input.to_owned())
+            .with_context("cause", err.to_string())
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -178,6 +336,62 @@ mod tests {
         assert!(err.is_permanent());
     }
 
+    #[test]
+    fn test_try_from_float_secs_error() {
+        let conv_err = std::time::Duration::try_from_secs_f64(-1.0).unwrap_err();
+        let err: Error = conv_err.into();
+        assert_eq!(err.kind(), &ErrorKind::Validation);
+        assert!(err.is_permanent());
+        assert!(err.message().starts_with("Duration conversion error: "));
+    }
+
+    #[cfg(feature = "humantime")]
+    #[test]
+    fn test_humantime_duration_error() {
+        fn timeout_from_config(raw: &str) -> Result<std::time::Duration, Error> {
+            Ok(humantime::parse_duration(raw)?)
+        }
+
+        assert_eq!(
+            timeout_from_config("1m 30s").unwrap(),
+            std::time::Duration::from_secs(90)
+        );
+        let err = timeout_from_config("ten seconds").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Validation);
+        assert!(err.is_permanent());
+    }
+
+    #[test]
+    fn test_try_from_int_error() {
+        let conv_err = u32::try_from(u64::MAX).unwrap_err();
+        let err: Error = conv_err.into();
+        assert_eq!(err.kind(), &ErrorKind::Validation);
+        assert!(err.is_permanent());
+    }
+
+    #[test]
+    fn test_char_try_from_error() {
+        let conv_err = char::try_from(0xD800u32).unwrap_err();
+        let err: Error = conv_err.into();
+        assert_eq!(err.kind(), &ErrorKind::Validation);
+        assert!(err.is_permanent());
+    }
+
+    #[test]
+    fn test_poison_error_is_fatal() {
+        let lock = std::sync::Arc::new(std::sync::Mutex::new(()));
+        let poisoner = std::sync::Arc::clone(&lock);
+        let _ = std::thread::spawn(move || {
+            let _guard = poisoner.lock().unwrap();
+            panic!("poison");
+        })
+        .join();
+
+        let err: Error = lock.lock().unwrap_err().into();
+        assert!(err.is_fatal());
+        assert!(!Error::from(io::Error::other("boom")).is_fatal());
+    }
+
     #[test]
     fn test_addr_parse_error() {
         let parse_err: std::net::AddrParseError =
@@ -186,4 +400,92 @@ mod tests {
         assert_eq!(err.kind(), &ErrorKind::Validation);
         assert!(err.is_permanent());
     }
+
+    #[test]
+    fn test_channel_errors() {
+        use std::sync::mpsc;
+        use std::time::Duration;
+
+        let (tx, rx) = mpsc::channel::<u32>();
+        let err: Error = rx.try_recv().unwrap_err().into();
+        assert_eq!(err.kind(), &ErrorKind::Unexpected);
+        assert!(err.is_retryable());
+
+        let err: Error = rx.recv_timeout(Duration::ZERO).unwrap_err().into();
+        assert_eq!(err.kind(), &ErrorKind::Timeout);
+        assert!(err.is_retryable());
+
+        drop(tx);
+        let err: Error = rx.recv().unwrap_err().into();
+        assert_eq!(err.kind(), &ErrorKind::Unexpected);
+        assert!(err.is_permanent());
+        let err: Error = rx.try_recv().unwrap_err().into();
+        assert!(err.is_permanent());
+
+        let (tx, rx) = mpsc::channel();
+        drop(rx);
+        let err: Error = tx.send(1).unwrap_err().into();
+        assert_eq!(err.kind(), &ErrorKind::Unexpected);
+        assert!(err.is_permanent());
+        assert_eq!(err.message(), "Channel closed: sending on a closed channel");
+    }
+
+    #[test]
+    fn test_parse_context() {
+        assert_eq!(parse_context::<u16>("8080", "port").unwrap(), 8080);
+        assert!(parse_context::<bool>("true", "enabled").unwrap());
+
+        let err = parse_context::<u16>("70000", "port").unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::Validation);
+        assert!(err.is_permanent());
+        assert_eq!(err.message(), "invalid port");
+        assert_eq!(
+            err.context(),
+            vec![
+                (Cow::Borrowed("field"), Cow::Borrowed("port")),
+                (Cow::Borrowed("value"), Cow::Borrowed("70000")),
+                (
+                    Cow::Borrowed("cause"),
+                    Cow::Borrowed("number too large to fit in target type")
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_map_err_to_keeps_cause() {
+        #[derive(Debug)]
+        struct QuotaError(u32);
+
+        impl fmt::Display for QuotaError {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                write!(f, "quota exceeded by {} units", self.0)
+            }
+        }
+
+        let result: Result<(), QuotaError> = Err(QuotaError(7));
+        let err = result
+            .map_err_to(
+                ErrorKind::PermissionDenied,
+                ErrorStatus::Permanent,
+                "quota check failed",
+            )
+            .unwrap_err();
+        assert_eq!(err.kind(), &ErrorKind::PermissionDenied);
+        assert_eq!(err.message(), "quota check failed");
+        assert_eq!(
+            err.context(),
+            vec![(
+                Cow::Borrowed("cause"),
+                Cow::Borrowed("quota exceeded by 7 units")
+            )]
+        );
+
+        let ok: Result<u8, QuotaError> = Ok(1);
+        assert_eq!(
+            ok.map_err_to(ErrorKind::Unexpected, ErrorStatus::Permanent, "x")
+                .unwrap(),
+            1
+        );
+    }
 }
@@ -1,5 +1,8 @@
 use std::fmt;
 
+use crate::kind::Severity;
+use crate::status::ErrorStatus;
+
 /// Database-specific error kinds.
 ///
 /// These errors categorize database-related failures by what the caller should do.
@@ -61,6 +64,30 @@ pub enum DatabaseErrorKind {
 }
 
 impl DatabaseErrorKind {
+    /// Returns every variant.
+    #[inline]
+    pub fn all() -> &'static [DatabaseErrorKind] {
+        &[
+            DatabaseErrorKind::ConnectionFailed,
+            DatabaseErrorKind::ConnectionLost,
+            DatabaseErrorKind::QuerySyntax,
+            DatabaseErrorKind::QueryExecution,
+            DatabaseErrorKind::ConstraintViolation,
+            DatabaseErrorKind::Deadlock,
+            DatabaseErrorKind::SerializationFailure,
+            DatabaseErrorKind::TransactionTimeout,
+            DatabaseErrorKind::NestedTransaction,
+            DatabaseErrorKind::NoRows,
+            DatabaseErrorKind::TooManyRows,
+            DatabaseErrorKind::TypeMismatch,
+            DatabaseErrorKind::SchemaMismatch,
+            DatabaseErrorKind::DatabaseLocked,
+            DatabaseErrorKind::DiskFull,
+            DatabaseErrorKind::PermissionDenied,
+            DatabaseErrorKind::ReadOnly,
+        ]
+    }
+
     /// Returns `true` if this database error kind represents a retryable condition.
     #[inline]
     pub fn is_retryable(&self) -> bool {
@@ -85,6 +112,27 @@ impl DatabaseErrorKind {
         }
     }
 
+    /// Returns the status matching [`is_retryable`](DatabaseErrorKind::is_retryable):
+    /// `Temporary` for retryable kinds, `Permanent` otherwise.
+    #[inline]
+    pub fn default_status(&self) -> ErrorStatus {
+        ErrorStatus::from_retryable(self.is_retryable())
+    }
+
+    /// Returns how many times this kind may be retried before it indicates a real problem.
+    ///
+    /// Non-retryable kinds return `Some(0)`; `None` means no per-kind cap applies.
+    #[inline]
+    pub fn max_retries(&self) -> Option<u32> {
+        match self {
+            DatabaseErrorKind::Deadlock => Some(3),
+            DatabaseErrorKind::SerializationFailure => Some(5),
+            DatabaseErrorKind::ConnectionFailed => Some(10),
+            k if !k.is_retryable() => Some(0),
+            _ => None,
+        }
+    }
+
     /// Returns `true` if this is a connection-related error.
     #[inline]
     pub fn is_connection_error(&self) -> bool {
@@ -157,6 +205,98 @@ impl DatabaseErrorKind {
         }
     }
 
+    /// Returns the default severity of this error kind.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        match self {
+            DatabaseErrorKind::NoRows => Severity::Info,
+            DatabaseErrorKind::ConstraintViolation => Severity::Warning,
+            DatabaseErrorKind::Deadlock => Severity::Warning,
+            DatabaseErrorKind::SerializationFailure => Severity::Warning,
+            DatabaseErrorKind::TransactionTimeout => Severity::Warning,
+            DatabaseErrorKind::DatabaseLocked => Severity::Warning,
+            DatabaseErrorKind::ConnectionFailed => Severity::Error,
+            DatabaseErrorKind::ConnectionLost => Severity::Error,
+            DatabaseErrorKind::QuerySyntax => Severity::Error,
+            DatabaseErrorKind::QueryExecution => Severity::Error,
+            DatabaseErrorKind::NestedTransaction => Severity::Error,
+            DatabaseErrorKind::TooManyRows => Severity::Error,
+            DatabaseErrorKind::TypeMismatch => Severity::Error,
+            DatabaseErrorKind::PermissionDenied => Severity::Error,
+            DatabaseErrorKind::ReadOnly => Severity::Error,
+            DatabaseErrorKind::SchemaMismatch => Severity::Critical,
+            DatabaseErrorKind::DiskFull => Severity::Critical,
+        }
+    }
+
+    /// Returns the HTTP status code to respond with for this error kind.
+    #[inline]
+    pub fn http_status(&self) -> u16 {
+        match self {
+            DatabaseErrorKind::ConstraintViolation => 409,
+            DatabaseErrorKind::NoRows => 404,
+            DatabaseErrorKind::PermissionDenied => 403,
+            DatabaseErrorKind::DiskFull => 507,
+            DatabaseErrorKind::TransactionTimeout => 504,
+            DatabaseErrorKind::ConnectionFailed
+            | DatabaseErrorKind::ConnectionLost
+            | DatabaseErrorKind::Deadlock
+            | DatabaseErrorKind::SerializationFailure
+            | DatabaseErrorKind::DatabaseLocked
+            | DatabaseErrorKind::ReadOnly => 503,
+            DatabaseErrorKind::QuerySyntax
+            | DatabaseErrorKind::QueryExecution
+            | DatabaseErrorKind::NestedTransaction
+            | DatabaseErrorKind::TooManyRows
+            | DatabaseErrorKind::TypeMismatch
+            | DatabaseErrorKind::SchemaMismatch => 500,
+        }
+    }
+
+    /// Creates a `DatabaseErrorKind` from a five-character SQLSTATE code.
+    ///
+    /// Returns `None` for malformed or unmapped codes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::db::DatabaseErrorKind;
+    ///
+    /// assert_eq!(
+    ///     DatabaseErrorKind::from_sqlstate("23505"),
+    ///     Some(DatabaseErrorKind::ConstraintViolation)
+    /// );
+    /// assert_eq!(
+    ///     DatabaseErrorKind::from_sqlstate("40P01"),
+    ///     Some(DatabaseErrorKind::Deadlock)
+    /// );
+    /// assert_eq!(DatabaseErrorKind::from_sqlstate("XX000"), None);
+    /// ```
+    pub fn from_sqlstate(code: &str) -> Option<Self> {
+        if code.len() != 5 || !code.is_ascii() {
+            return None;
+        }
+        let kind = match code {
+            "08003" | "08006" => DatabaseErrorKind::ConnectionLost,
+            "25001" => DatabaseErrorKind::NestedTransaction,
+            "25006" => DatabaseErrorKind::ReadOnly,
+            "40001" => DatabaseErrorKind::SerializationFailure,
+            "40P01" => DatabaseErrorKind::Deadlock,
+            "42501" => DatabaseErrorKind::PermissionDenied,
+            "53100" => DatabaseErrorKind::DiskFull,
+            "55P03" => DatabaseErrorKind::DatabaseLocked,
+            "57014" => DatabaseErrorKind::TransactionTimeout,
+            _ => match &code[..2] {
+                "08" => DatabaseErrorKind::ConnectionFailed,
+                "22" => DatabaseErrorKind::QueryExecution,
+                "23" => DatabaseErrorKind::ConstraintViolation,
+                "42" => DatabaseErrorKind::QuerySyntax,
+                _ => return None,
+            },
+        };
+        Some(kind)
+    }
+
     /// Returns a machine-readable string representation of this database error kind.
     #[inline]
     pub fn to_machine_string(&self) -> &'static str {
@@ -180,6 +320,31 @@ impl DatabaseErrorKind {
             DatabaseErrorKind::ReadOnly => "read_only",
         }
     }
+
+    /// Parses the output of [`DatabaseErrorKind::to_machine_string`] back into a kind.
+    pub fn from_machine_string(s: &str) -> Option<Self> {
+        let kind = match s {
+            "connection_failed" => DatabaseErrorKind::ConnectionFailed,
+            "connection_lost" => DatabaseErrorKind::ConnectionLost,
+            "query_syntax" => DatabaseErrorKind::QuerySyntax,
+            "query_execution" => DatabaseErrorKind::QueryExecution,
+            "constraint_violation" => DatabaseErrorKind::ConstraintViolation,
+            "deadlock" => DatabaseErrorKind::Deadlock,
+            "serialization_failure" => DatabaseErrorKind::SerializationFailure,
+            "transaction_timeout" => DatabaseErrorKind::TransactionTimeout,
+            "nested_transaction" => DatabaseErrorKind::NestedTransaction,
+            "no_rows" => DatabaseErrorKind::NoRows,
+            "too_many_rows" => DatabaseErrorKind::TooManyRows,
+            "type_mismatch" => DatabaseErrorKind::TypeMismatch,
+            "schema_mismatch" => DatabaseErrorKind::SchemaMismatch,
+            "database_locked" => DatabaseErrorKind::DatabaseLocked,
+            "disk_full" => DatabaseErrorKind::DiskFull,
+            "permission_denied" => DatabaseErrorKind::PermissionDenied,
+            "read_only" => DatabaseErrorKind::ReadOnly,
+            _ => return None,
+        };
+        Some(kind)
+    }
 }
 
 impl fmt::Display for DatabaseErrorKind {
@@ -209,6 +374,26 @@ impl fmt::Display for DatabaseErrorKind {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{
+        assert_default_status_matches_retryability, assert_lists_every_variant,
+    };
+
+    #[test]
+    fn test_default_status_matches_retryability() {
+        assert_default_status_matches_retryability(
+            DatabaseErrorKind::all(),
+            DatabaseErrorKind::default_status,
+            DatabaseErrorKind::is_retryable,
+        );
+        assert_eq!(
+            DatabaseErrorKind::Deadlock.default_status(),
+            ErrorStatus::Temporary
+        );
+        assert_eq!(
+            DatabaseErrorKind::ConstraintViolation.default_status(),
+            ErrorStatus::Permanent
+        );
+    }
 
     #[test]
     fn test_connection_failed_retryable() {
@@ -230,6 +415,46 @@ mod tests {
         assert!(DatabaseErrorKind::Deadlock.is_retryable());
     }
 
+    #[test]
+    fn test_max_retries() {
+        assert_eq!(DatabaseErrorKind::Deadlock.max_retries(), Some(3));
+        assert_eq!(
+            DatabaseErrorKind::SerializationFailure.max_retries(),
+            Some(5)
+        );
+        assert_eq!(DatabaseErrorKind::ConnectionFailed.max_retries(), Some(10));
+        assert_eq!(DatabaseErrorKind::QuerySyntax.max_retries(), Some(0));
+        assert_eq!(DatabaseErrorKind::ConnectionLost.max_retries(), None);
+        for kind in DatabaseErrorKind::all() {
+            if !kind.is_retryable() {
+                assert_eq!(kind.max_retries(), Some(0), "{kind:?}");
+            }
+        }
+    }
+
+    #[test]
+    fn test_all_covers_every_variant() {
+        assert_lists_every_variant(DatabaseErrorKind::all(), 17, |kind| match kind {
+            DatabaseErrorKind::ConnectionFailed => 0,
+            DatabaseErrorKind::ConnectionLost => 1,
+            DatabaseErrorKind::QuerySyntax => 2,
+            DatabaseErrorKind::QueryExecution => 3,
+            DatabaseErrorKind::ConstraintViolation => 4,
+            DatabaseErrorKind::Deadlock => 5,
+            DatabaseErrorKind::SerializationFailure => 6,
+            DatabaseErrorKind::TransactionTimeout => 7,
+            DatabaseErrorKind::NestedTransaction => 8,
+            DatabaseErrorKind::NoRows => 9,
+            DatabaseErrorKind::TooManyRows => 10,
+            DatabaseErrorKind::TypeMismatch => 11,
+            DatabaseErrorKind::SchemaMismatch => 12,
+            DatabaseErrorKind::DatabaseLocked => 13,
+            DatabaseErrorKind::DiskFull => 14,
+            DatabaseErrorKind::PermissionDenied => 15,
+            DatabaseErrorKind::ReadOnly => 16,
+        });
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -242,4 +467,43 @@ mod tests {
         );
         assert_eq!(DatabaseErrorKind::Deadlock.to_string(), "deadlock detected");
     }
+
+    #[test]
+    fn test_http_status() {
+        assert_eq!(DatabaseErrorKind::ConstraintViolation.http_status(), 409);
+        assert_eq!(DatabaseErrorKind::Deadlock.http_status(), 503);
+        assert_eq!(
+            crate::ErrorKind::Database(DatabaseErrorKind::ConstraintViolation).http_status(),
+            409
+        );
+    }
+
+    #[test]
+    fn test_from_sqlstate() {
+        let cases = [
+            ("08001", DatabaseErrorKind::ConnectionFailed),
+            ("08006", DatabaseErrorKind::ConnectionLost),
+            ("22P02", DatabaseErrorKind::QueryExecution),
+            ("23505", DatabaseErrorKind::ConstraintViolation),
+            ("25001", DatabaseErrorKind::NestedTransaction),
+            ("25006", DatabaseErrorKind::ReadOnly),
+            ("40001", DatabaseErrorKind::SerializationFailure),
+            ("40P01", DatabaseErrorKind::Deadlock),
+            ("42601", DatabaseErrorKind::QuerySyntax),
+            ("42501", DatabaseErrorKind::PermissionDenied),
+            ("53100", DatabaseErrorKind::DiskFull),
+            ("55P03", DatabaseErrorKind::DatabaseLocked),
+            ("57014", DatabaseErrorKind::TransactionTimeout),
+        ];
+        for (code, kind) in cases {
+            assert_eq!(DatabaseErrorKind::from_sqlstate(code), Some(kind), "{code}");
+        }
+    }
+
+    #[test]
+    fn test_from_sqlstate_unknown() {
+        assert_eq!(DatabaseErrorKind::from_sqlstate("XX000"), None);
+        assert_eq!(DatabaseErrorKind::from_sqlstate("235"), None);
+        assert_eq!(DatabaseErrorKind::from_sqlstate(""), None);
+    }
 }
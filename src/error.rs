@@ -1,15 +1,124 @@
+use std::any::Any;
 use std::borrow::Cow;
+use std::collections::BTreeMap;
 use std::error::Error as ErrorTrait;
 use std::fmt::{self, Write};
-use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU8, AtomicUsize, Ordering};
+use std::sync::{Arc, RwLock};
 
-use crate::{ErrorKind, ErrorStatus};
+use crate::status::RetryDecision;
+use crate::visit::{ByteBuf, JsonVisitor, MachineVisitor, write_escaped};
+use crate::{ErrorKind, ErrorStatus, Severity};
 
 pub use self::builder::ErrorBuilder;
 
+/// Type-erased error for boundaries that only accept boxed errors.
+pub type BoxError = Box<dyn std::error::Error + Send + Sync + 'static>;
+
 /// Type alias for context storage.
 pub type ContextVec = smallvec::SmallVec<[(Cow<'static, str>, Cow<'static, str>); 1]>;
 
+/// Context key of the marker appended when pairs are dropped by the context cap.
+pub const TRUNCATED_KEY: &str = "truncated";
+
+/// Context key of the retry hint, in whole milliseconds.
+///
+/// Set it with [`Error::with_retry_after`] and read it with [`Error::retry_after`].
+pub const RETRY_AFTER_KEY: &str = "retry_after_ms";
+
+static STRICT_CONTEXT_KEYS: AtomicBool = AtomicBool::new(false);
+
+/// Makes empty or whitespace-only context keys a bug rather than a silent skip.
+///
+/// Such keys are always dropped. In strict mode, debug builds additionally
+/// panic so the offending call site is caught early.
+pub fn set_strict_context_keys(strict: bool) {
+    STRICT_CONTEXT_KEYS.store(strict, Ordering::Relaxed);
+}
+
+/// Rewrites a context key containing control characters; `None` drops the pair.
+pub type KeySanitizer = fn(&str) -> Option<String>;
+
+static KEY_SANITIZER: RwLock<KeySanitizer> = RwLock::new(strip_control_chars);
+
+/// Replaces the process-wide sanitizer for context keys with control characters.
+///
+/// Newlines and other control characters in keys break logfmt and most log
+/// aggregators. The sanitizer only runs for such keys; the default removes the
+/// offending characters. Values are escaped by the serializers instead.
+pub fn set_context_key_sanitizer(sanitizer: KeySanitizer) {
+    *KEY_SANITIZER.write().unwrap_or_else(|e| e.into_inner()) = sanitizer;
+}
+
+fn strip_control_chars(key: &str) -> Option<String> {
+    Some(key.chars().filter(|c| !c.is_control()).collect())
+}
+
+static MAX_CONTEXT: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Sets the process-wide maximum number of context pairs kept per error.
+///
+/// Once an error holds `max` pairs, further pairs are dropped and a single
+/// `("truncated", "true")` marker is appended instead. Defaults to unlimited.
+pub fn set_max_context(max: usize) {
+    MAX_CONTEXT.store(max, Ordering::Relaxed);
+}
+
+/// Returns the current process-wide context cap.
+#[inline]
+pub fn max_context() -> usize {
+    MAX_CONTEXT.load(Ordering::Relaxed)
+}
+
+/// Suffix appended to context values cut by [`set_max_context_value_len`].
+pub const TRUNCATED_VALUE_SUFFIX: &str = "…(truncated)";
+
+static MAX_CONTEXT_VALUE_LEN: AtomicUsize = AtomicUsize::new(usize::MAX);
+
+/// Sets the process-wide maximum length, in characters, of a context value.
+///
+/// Longer values keep their first `max` characters followed by
+/// [`TRUNCATED_VALUE_SUFFIX`]. Defaults to unlimited.
+pub fn set_max_context_value_len(max: usize) {
+    MAX_CONTEXT_VALUE_LEN.store(max, Ordering::Relaxed);
+}
+
+/// Returns the current process-wide context value length cap.
+#[inline]
+pub fn max_context_value_len() -> usize {
+    MAX_CONTEXT_VALUE_LEN.load(Ordering::Relaxed)
+}
+
+/// Occurrence counts at which [`Error::effective_severity`] escalates one level each.
+pub const ESCALATION_THRESHOLDS: [u64; 2] = [1_000, 100_000];
+
+static MIN_LOG_SEVERITY: AtomicU8 = AtomicU8::new(Severity::Info as u8);
+
+/// Sets the process-wide minimum severity for [`Error::emit`]. Defaults to `Info`.
+pub fn set_min_log_severity(min: Severity) {
+    MIN_LOG_SEVERITY.store(min as u8, Ordering::Relaxed);
+}
+
+/// Returns the current process-wide minimum log severity.
+pub fn min_log_severity() -> Severity {
+    match MIN_LOG_SEVERITY.load(Ordering::Relaxed) {
+        0 => Severity::Info,
+        1 => Severity::Warning,
+        2 => Severity::Error,
+        _ => Severity::Critical,
+    }
+}
+
+/// Trace and span identifiers correlating an error with a distributed trace.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct TraceContext {
+    /// The 16-byte trace identifier.
+    pub trace_id: [u8; 16],
+    /// The 8-byte span identifier.
+    pub span_id: [u8; 8],
+}
+
 /// Core error type for the erract library.
 #[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 #[derive(Debug, Clone)]
@@ -18,17 +127,79 @@ pub struct Error {
     status: ErrorStatus,
     message: Cow<'static, str>,
     operation: Option<&'static str>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(flatten, default, deserialize_with = "deserialize_labels")
+    )]
+    labels: Option<Box<Labels>>,
     pub(crate) context: crate::arena::ContextHandle,
     #[cfg_attr(feature = "serde", serde(skip, default))]
+    attachments: Option<Box<Attachments>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    attempt: Option<u32>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    fatal: bool,
+    #[cfg_attr(feature = "serde", serde(default))]
+    severity_override: Option<u8>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    trace: Option<Box<TraceContext>>,
+    #[cfg_attr(feature = "serde", serde(default))]
+    retry_decision: Option<Box<RetryDecision>>,
+}
+
+/// Rarely set identifiers and counters, boxed to keep [`Error`] small.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+struct Labels {
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    code: Option<Cow<'static, str>>,
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    message_id: Option<&'static str>,
+    /// First operation, recorded once `operation` is overwritten.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    origin: Option<&'static str>,
+    /// How often this error recurred, for frequency-based severity.
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Option::is_none")
+    )]
+    occurrences: Option<u64>,
+    /// Set on frames raised only to carry context; see [`Error::is_context_frame`].
+    #[cfg_attr(feature = "serde", serde(skip))]
+    context_frame: bool,
+}
+
+/// Reads flattened labels, keeping `None` when none are present so that
+/// deserialized errors compare equal to freshly built ones.
+#[cfg(feature = "serde")]
+fn deserialize_labels<'de, D>(deserializer: D) -> Result<Option<Box<Labels>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+    Labels: serde::Deserialize<'de>,
+{
+    let labels = <Labels as serde::Deserialize>::deserialize(deserializer)?;
+    Ok((labels != Labels::default()).then(|| Box::new(labels)))
+}
+
+/// Rarely set, non-serializable payloads, boxed to keep [`Error`] small.
+#[derive(Debug, Clone, Default)]
+struct Attachments {
     source: Option<Arc<dyn std::error::Error + Send + Sync + 'static>>,
+    details: Vec<Arc<dyn Any + Send + Sync>>,
 }
 
 impl PartialEq for Error {
     fn eq(&self, other: &Self) -> bool {
-        self.kind == other.kind
-            && self.status == other.status
-            && self.message == other.message
-            && self.operation == other.operation
+        self.eq_except_context(other)
             && matches!(
                 (&self.context, &other.context),
                 (
@@ -41,6 +212,20 @@ impl PartialEq for Error {
 
 impl Eq for Error {}
 
+impl PartialEq<ErrorKind> for Error {
+    #[inline]
+    fn eq(&self, other: &ErrorKind) -> bool {
+        self.kind == *other
+    }
+}
+
+impl PartialEq<ErrorStatus> for Error {
+    #[inline]
+    fn eq(&self, other: &ErrorStatus) -> bool {
+        self.status == *other
+    }
+}
+
 impl Error {
     /// Creates a "not found" error with zero message allocation.
     #[inline]
@@ -60,6 +245,33 @@ impl Error {
         Self::temporary_static(ErrorKind::Timeout, "operation timed out")
     }
 
+    /// Creates a temporary timeout error recording how long was waited.
+    ///
+    /// Attaches `elapsed_ms` and `limit_ms`, plus a [retry hint](Error::retry_after)
+    /// 10% (at least 1ms) above the limit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::time::Duration;
+    /// use erract::Error;
+    ///
+    /// let error = Error::timeout_after(Duration::from_millis(5012), Duration::from_secs(5));
+    /// assert_eq!(error.to_string(), "timed out after 5012ms (limit 5000ms) [elapsed_ms: 5012, limit_ms: 5000, retry_after_ms: 5500]");
+    /// assert_eq!(error.retry_after(), Some(Duration::from_millis(5500)));
+    /// ```
+    pub fn timeout_after(elapsed: std::time::Duration, limit: std::time::Duration) -> Self {
+        let retry_after = limit + (limit / 10).max(std::time::Duration::from_millis(1));
+        let (elapsed_ms, limit_ms) = (elapsed.as_millis(), limit.as_millis());
+        Self::temporary(
+            ErrorKind::Timeout,
+            format!("timed out after {elapsed_ms}ms (limit {limit_ms}ms)"),
+        )
+        .with_context_value("elapsed_ms", elapsed_ms)
+        .with_context_value("limit_ms", limit_ms)
+        .with_retry_after(retry_after)
+    }
+
     /// Creates a "validation failed" error with zero message allocation.
     #[inline]
     pub fn validation_failed() -> Self {
@@ -75,79 +287,37 @@ impl Error {
     /// Creates a permanent error with a static message (zero allocation).
     #[inline]
     pub fn permanent_static(kind: ErrorKind, message: &'static str) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Permanent,
-            message: Cow::Borrowed(message),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Permanent, Cow::Borrowed(message))
     }
 
     /// Creates a temporary error with a static message (zero allocation).
     #[inline]
     pub fn temporary_static(kind: ErrorKind, message: &'static str) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Temporary,
-            message: Cow::Borrowed(message),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Temporary, Cow::Borrowed(message))
     }
 
     /// Creates a persistent error with a static message (zero allocation).
     #[inline]
     pub fn persistent_static(kind: ErrorKind, message: &'static str) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Persistent,
-            message: Cow::Borrowed(message),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Persistent, Cow::Borrowed(message))
     }
 
     /// Creates a permanent error that cannot be retried.
     #[inline]
     pub fn permanent(kind: ErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Permanent,
-            message: message.into(),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Permanent, message)
     }
 
     /// Creates a temporary error that is safe to retry.
     #[inline]
     pub fn temporary(kind: ErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Temporary,
-            message: message.into(),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Temporary, message)
     }
 
     /// Creates a persistent error that was already retried.
     #[inline]
     pub fn persistent(kind: ErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
-        Error {
-            kind,
-            status: ErrorStatus::Persistent,
-            message: message.into(),
-            operation: None,
-            context: crate::arena::ContextHandle::Empty,
-            source: None,
-        }
+        Error::new(kind, ErrorStatus::Persistent, message)
     }
 
     /// Creates an error with a specific status.
@@ -156,17 +326,145 @@ impl Error {
         kind: ErrorKind,
         status: ErrorStatus,
         message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        Error::new_detached(kind, status, message).with_ambient_context()
+    }
+
+    /// Like [`Error::new`], but ignores ambient context scopes; for errors
+    /// derived from another error, which already carries that context.
+    #[inline]
+    pub(crate) fn new_detached(
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: impl Into<Cow<'static, str>>,
     ) -> Self {
         Error {
             kind,
             status,
             message: message.into(),
             operation: None,
+            labels: None,
             context: crate::arena::ContextHandle::Empty,
-            source: None,
+            attachments: None,
+            attempt: None,
+            fatal: false,
+            severity_override: None,
+            trace: None,
+            retry_decision: None,
         }
     }
 
+    /// Creates a frame that wraps `inner` only to carry extra context.
+    ///
+    /// It repeats `inner`'s kind and status, so tree predicates are unaffected,
+    /// and leaves the message empty.
+    pub(crate) fn context_frame(inner: &Error) -> Self {
+        let mut error = Error::new_detached(inner.kind.clone(), inner.status.clone(), "");
+        error.labels = Some(Box::new(Labels {
+            context_frame: true,
+            ..Labels::default()
+        }));
+        error
+    }
+
+    #[cfg(feature = "ambient")]
+    #[inline]
+    fn with_ambient_context(mut self) -> Self {
+        let pairs = crate::context::ambient_pairs();
+        if !pairs.is_empty() {
+            let mut v = Vec::with_capacity(pairs.len());
+            for (key, value) in pairs {
+                push_capped(&mut v, (Cow::Borrowed(key), Cow::Borrowed(value)));
+            }
+            self.replace_context(v);
+        }
+        self
+    }
+
+    #[cfg(not(feature = "ambient"))]
+    #[inline(always)]
+    fn with_ambient_context(self) -> Self {
+        self
+    }
+
+    /// Creates an HTTP error whose status follows
+    /// [`HttpErrorKind::default_status`](crate::http::HttpErrorKind::default_status).
+    #[cfg(feature = "http")]
+    #[inline]
+    pub fn http(kind: crate::http::HttpErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
+        let status = kind.default_status();
+        Error::new(ErrorKind::Http(kind), status, message)
+    }
+
+    /// Creates an error from a failed HTTP response's status code and headers.
+    ///
+    /// A `Retry-After` in delta-seconds becomes the [retry hint](Error::retry_after), and
+    /// `X-Request-Id`/`X-Trace-Id` become `request_id`/`trace_id` context. Header
+    /// names match case-insensitively; missing or malformed headers are skipped.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let error = Error::from_http(503, &[("Retry-After", "30"), ("X-Request-Id", "req-1")]);
+    /// assert!(error.is_retryable());
+    /// assert_eq!(error.to_string(), "server error: 503 [retry_after_ms: 30000, request_id: req-1]");
+    /// assert_eq!(error.retry_after(), Some(std::time::Duration::from_secs(30)));
+    /// ```
+    #[cfg(feature = "http")]
+    pub fn from_http(status: u16, headers: &[(&str, &str)]) -> Self {
+        let header = |name: &str| {
+            headers
+                .iter()
+                .find(|(key, _)| key.trim().eq_ignore_ascii_case(name))
+                .map(|(_, value)| value.trim())
+                .filter(|value| !value.is_empty())
+        };
+        let kind = crate::http::HttpErrorKind::from_status(status);
+        let mut error = Error::http(kind.clone(), kind.to_string());
+        if let Some(secs) = header("retry-after").and_then(|value| value.parse::<u64>().ok()) {
+            error = error.with_retry_after(std::time::Duration::from_secs(secs));
+        }
+        if let Some(id) = header("x-request-id") {
+            error = error.with_context("request_id", id.to_owned());
+        }
+        if let Some(id) = header("x-trace-id") {
+            error = error.with_context("trace_id", id.to_owned());
+        }
+        error
+    }
+
+    /// Creates a database error whose status follows
+    /// [`DatabaseErrorKind::default_status`](crate::db::DatabaseErrorKind::default_status).
+    #[cfg(feature = "db")]
+    #[inline]
+    pub fn database(
+        kind: crate::db::DatabaseErrorKind,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let status = kind.default_status();
+        Error::new(ErrorKind::Database(kind), status, message)
+    }
+
+    /// Creates a storage error whose status follows
+    /// [`StorageErrorKind::default_status`](crate::storage::StorageErrorKind::default_status).
+    #[cfg(feature = "storage")]
+    #[inline]
+    pub fn storage(
+        kind: crate::storage::StorageErrorKind,
+        message: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let status = kind.default_status();
+        Error::new(ErrorKind::Storage(kind), status, message)
+    }
+
+    /// Creates a fatal error that should terminate the process rather than be handled.
+    #[inline]
+    pub fn fatal(kind: ErrorKind, message: impl Into<Cow<'static, str>>) -> Self {
+        Error::permanent(kind, message).mark_fatal()
+    }
+
     /// Returns a builder for configuring this error.
     #[inline]
     pub fn builder(
@@ -195,29 +493,124 @@ impl Error {
         &self.message
     }
 
+    /// Returns only the message, without operation or context.
+    ///
+    /// For errors without operation or context this equals the `Display`
+    /// output, letting hot logging paths skip formatting altogether.
+    #[inline(always)]
+    pub fn message_only(&self) -> &str {
+        &self.message
+    }
+
     /// Returns the operation name if set.
     #[inline(always)]
     pub fn operation(&self) -> Option<&'static str> {
         self.operation
     }
 
+    /// Returns the first operation ever set on this error.
+    ///
+    /// Unlike [`operation`](Error::operation), later calls to
+    /// [`with_operation`](Error::with_operation) do not replace it, so it names
+    /// where the error originated rather than the last wrapper.
+    #[inline]
+    pub fn origin_operation(&self) -> Option<&'static str> {
+        self.recorded_origin().or(self.operation)
+    }
+
+    /// Returns the origin recorded when the operation was replaced, without the
+    /// fallback to `operation`, so serialized output does not repeat it.
+    #[inline]
+    pub(crate) fn recorded_origin(&self) -> Option<&'static str> {
+        self.labels.as_ref().and_then(|l| l.origin)
+    }
+
+    /// Returns the application-specific error code, if set.
+    #[inline(always)]
+    pub fn code(&self) -> Option<&str> {
+        self.labels.as_ref()?.code.as_deref()
+    }
+
+    /// Returns the ID a frontend can use to look up a localized message, if set.
+    #[inline]
+    pub fn message_id(&self) -> Option<&'static str> {
+        self.labels.as_ref()?.message_id
+    }
+
+    /// Returns the first attached detail of type `T`, if any.
+    pub fn detail<T: Any>(&self) -> Option<&T> {
+        self.attachments
+            .as_deref()?
+            .details
+            .iter()
+            .find_map(|detail| detail.downcast_ref::<T>())
+    }
+
+    /// Returns the attempt number on which this error occurred, if recorded.
+    #[inline(always)]
+    pub fn attempt(&self) -> Option<u32> {
+        self.attempt
+    }
+
+    /// Returns the retry hint, if one was recorded under [`RETRY_AFTER_KEY`].
+    ///
+    /// The last hint wins; a value that is not whole milliseconds is ignored.
+    pub fn retry_after(&self) -> Option<std::time::Duration> {
+        self.context()
+            .iter()
+            .rev()
+            .find(|(k, _)| k == RETRY_AFTER_KEY)
+            .and_then(|(_, v)| v.parse().ok())
+            .map(std::time::Duration::from_millis)
+    }
+
+    /// Returns the trace and span identifiers, if recorded.
+    #[inline]
+    pub fn trace_context(&self) -> Option<&TraceContext> {
+        self.trace.as_deref()
+    }
+
+    /// Returns why a retry driver stopped on this error, if one recorded it.
+    #[inline]
+    pub fn retry_decision(&self) -> Option<&RetryDecision> {
+        self.retry_decision.as_deref()
+    }
+
     /// Returns the context key-value pairs.
     pub fn context(&self) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
-        match &self.context {
-            crate::arena::ContextHandle::Heap(v) => v.to_vec(),
-            crate::arena::ContextHandle::Arena {
-                offset,
-                len,
-                thread_id,
-            } => {
-                if *thread_id == crate::arena::current_thread_id() {
-                    crate::arena::with_arena(|arena| arena.get_pairs(*offset, *len))
-                } else {
-                    Vec::new()
-                }
-            }
-            _ => Vec::new(),
+        self.context.to_vec()
+    }
+
+    /// Returns the commonly logged fields in one struct, for logging adapters.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::{Error, ErrorFields};
+    ///
+    /// let error = Error::timeout().with_operation("fetch");
+    /// let ErrorFields { operation, retryable, .. } = error.fields();
+    /// assert_eq!((operation, retryable), (Some("fetch"), true));
+    /// ```
+    pub fn fields(&self) -> ErrorFields<'_> {
+        ErrorFields::from(self)
+    }
+
+    /// Returns the context as a map, with later duplicate keys overwriting earlier ones.
+    pub fn context_map(&self) -> BTreeMap<String, String> {
+        self.context()
+            .into_iter()
+            .map(|(k, v)| (k.into_owned(), v.into_owned()))
+            .collect()
+    }
+
+    /// Returns the context as a map, keeping every value for duplicate keys in insertion order.
+    pub fn context_map_multi(&self) -> BTreeMap<String, Vec<String>> {
+        let mut map: BTreeMap<String, Vec<String>> = BTreeMap::new();
+        for (k, v) in self.context() {
+            map.entry(k.into_owned()).or_default().push(v.into_owned());
         }
+        map
     }
 
     /// Returns `true` if this error is safe to retry.
@@ -232,6 +625,121 @@ impl Error {
         self.status.is_permanent()
     }
 
+    /// Orders errors by importance: kind [`severity`](ErrorKind::severity) first,
+    /// then status, where permanent outranks persistent, which outranks temporary.
+    pub fn severity_cmp(&self, other: &Error) -> std::cmp::Ordering {
+        self.kind
+            .severity()
+            .cmp(&other.kind.severity())
+            .then_with(|| self.status.rank().cmp(&other.status.rank()))
+    }
+
+    /// Returns this error's 0–100 severity score for triage ranking.
+    ///
+    /// This is the [`with_severity_override`](Error::with_severity_override) score
+    /// when set, and the kind's [`severity_score`](ErrorKind::severity_score) otherwise.
+    #[inline]
+    pub fn severity_score(&self) -> u8 {
+        self.severity_override
+            .unwrap_or_else(|| self.kind.severity_score())
+    }
+
+    /// Overrides the kind's default severity score; scores above 100 are capped.
+    #[inline]
+    #[must_use]
+    pub fn with_severity_override(mut self, score: u8) -> Self {
+        self.severity_override = Some(score.min(100));
+        self
+    }
+
+    /// Records how many times this error occurred, e.g. within an alerting window.
+    ///
+    /// Frequent errors escalate their [`effective_severity`](Error::effective_severity).
+    #[inline]
+    #[must_use]
+    pub fn with_occurrence_count(mut self, n: u64) -> Self {
+        self.labels.get_or_insert_with(Default::default).occurrences = Some(n);
+        self
+    }
+
+    /// Returns the occurrence count, if recorded.
+    #[inline]
+    pub fn occurrence_count(&self) -> Option<u64> {
+        self.labels.as_deref()?.occurrences
+    }
+
+    /// Returns the kind's [`severity`](ErrorKind::severity), escalated by how often
+    /// the error occurred.
+    ///
+    /// Each threshold in [`ESCALATION_THRESHOLDS`] reached raises the severity
+    /// one level, up to [`Severity::Critical`].
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::{Error, Severity};
+    ///
+    /// assert_eq!(Error::not_found().effective_severity(), Severity::Warning);
+    /// let frequent = Error::not_found().with_occurrence_count(1_000);
+    /// assert_eq!(frequent.effective_severity(), Severity::Error);
+    /// ```
+    pub fn effective_severity(&self) -> Severity {
+        let base = self.kind.severity();
+        let Some(count) = self.occurrence_count() else {
+            return base;
+        };
+        let steps = ESCALATION_THRESHOLDS
+            .iter()
+            .filter(|&&threshold| count >= threshold)
+            .count();
+        (0..steps).fold(base, |severity, _| match severity {
+            Severity::Info => Severity::Warning,
+            Severity::Warning => Severity::Error,
+            Severity::Error | Severity::Critical => Severity::Critical,
+        })
+    }
+
+    /// Returns `true` if the [`effective_severity`](Error::effective_severity) is at least `min`.
+    #[inline]
+    pub fn should_log(&self, min: Severity) -> bool {
+        self.effective_severity() >= min
+    }
+
+    /// Logs this error through the `log` crate unless it is below [`min_log_severity`].
+    ///
+    /// The log level follows the severity: `Info` logs at info, `Warning` at warn,
+    /// `Error` and `Critical` at error. Returns whether the error was logged.
+    #[cfg(feature = "log")]
+    pub fn emit(&self) -> bool {
+        let severity = self.effective_severity();
+        if !self.should_log(min_log_severity()) {
+            return false;
+        }
+        let level = match severity {
+            Severity::Info => log::Level::Info,
+            Severity::Warning => log::Level::Warn,
+            Severity::Error | Severity::Critical => log::Level::Error,
+        };
+        log::log!(target: "erract", level, "{}: {self}", self.kind);
+        true
+    }
+
+    /// Returns `true` if this error is unrecoverable, such as corruption or a poisoned lock.
+    #[inline(always)]
+    pub fn is_fatal(&self) -> bool {
+        self.fatal
+    }
+
+    /// Returns `true` for frames raised only to attach context to an error tree,
+    /// such as those added by [`try_context!`](crate::try_context).
+    ///
+    /// They have no message of their own and are not counted by
+    /// [`count_errors`](crate::extract::count_errors).
+    #[inline]
+    pub fn is_context_frame(&self) -> bool {
+        self.labels.as_ref().is_some_and(|l| l.context_frame)
+    }
+
     /// Returns an iterator over context key-value pairs.
     pub fn iter_context(&self) -> Vec<(String, String)> {
         self.context()
@@ -244,7 +752,47 @@ impl Error {
     #[inline]
     #[must_use]
     pub fn with_operation(mut self, operation: &'static str) -> Self {
-        self.operation = Some(operation);
+        if let Some(previous) = self.operation.replace(operation)
+            && previous != operation
+        {
+            let labels = self.labels.get_or_insert_with(Default::default);
+            labels.origin.get_or_insert(previous);
+        }
+        self
+    }
+
+    /// Sets an application-specific error code such as `"E1001"`.
+    ///
+    /// Unlike the kind, the code is a stable, user-defined string that clients
+    /// can switch on, e.g. to pick a localized message.
+    #[inline]
+    #[must_use]
+    pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+        self.labels.get_or_insert_with(Default::default).code = Some(code.into());
+        self
+    }
+
+    /// Sets a message ID such as `"error.user_not_found"` for localization.
+    ///
+    /// The message itself stays as-is for logs; a frontend looks up the
+    /// localized text by this ID and falls back to the message.
+    #[inline]
+    #[must_use]
+    pub fn with_message_id(mut self, id: &'static str) -> Self {
+        self.labels.get_or_insert_with(Default::default).message_id = Some(id);
+        self
+    }
+
+    /// Attaches a typed detail payload, such as a structured quota failure.
+    ///
+    /// Details are shared on clone and survive propagation; retrieve them with
+    /// [`Error::detail`]. They are not serialized.
+    #[must_use]
+    pub fn with_detail<T: Any + Send + Sync>(mut self, detail: T) -> Self {
+        self.attachments
+            .get_or_insert_with(Default::default)
+            .details
+            .push(Arc::new(detail));
         self
     }
 
@@ -255,11 +803,82 @@ impl Error {
     where
         E: std::error::Error + Send + Sync + 'static,
     {
-        self.source = Some(Arc::new(source));
+        self.attachments.get_or_insert_with(Default::default).source = Some(Arc::new(source));
+        self
+    }
+
+    /// Marks this error as fatal.
+    #[inline]
+    #[must_use]
+    pub fn mark_fatal(mut self) -> Self {
+        self.fatal = true;
+        self
+    }
+
+    /// Marks this error as having exhausted its retries.
+    ///
+    /// A temporary error becomes [`ErrorStatus::Persistent`], so it no longer counts
+    /// as retryable once re-wrapped, and a `retry_exhausted = true` context pair is set.
+    /// Permanent errors keep their status.
+    #[must_use]
+    pub fn mark_exhausted(mut self) -> Self {
+        if self.status.is_retryable() {
+            self.status = ErrorStatus::Persistent;
+        }
+        self.set_context("retry_exhausted", "true")
+    }
+
+    /// Records the trace and span identifiers active when this error occurred.
+    ///
+    /// All-zero identifiers mean no span is active and clear the trace context.
+    #[inline]
+    #[must_use]
+    pub fn with_trace_context(mut self, trace_id: [u8; 16], span_id: [u8; 8]) -> Self {
+        self.trace = if trace_id == [0; 16] || span_id == [0; 8] {
+            None
+        } else {
+            Some(Box::new(TraceContext { trace_id, span_id }))
+        };
+        self
+    }
+
+    /// Records the identifiers of the current OpenTelemetry span, if one is active.
+    #[cfg(feature = "otel")]
+    #[must_use]
+    pub fn with_current_trace(self) -> Self {
+        use opentelemetry::trace::TraceContextExt;
+
+        let context = opentelemetry::Context::current();
+        let span = context.span();
+        let span_context = span.span_context();
+        if !span_context.is_valid() {
+            return self;
+        }
+        self.with_trace_context(
+            span_context.trace_id().to_bytes(),
+            span_context.span_id().to_bytes(),
+        )
+    }
+
+    /// Records the attempt number on which this error occurred.
+    #[inline]
+    #[must_use]
+    pub fn with_attempt(mut self, attempt: u32) -> Self {
+        self.attempt = Some(attempt);
+        self
+    }
+
+    /// Records why a retry driver stopped on this error.
+    #[inline]
+    #[must_use]
+    pub fn with_retry_decision(mut self, decision: RetryDecision) -> Self {
+        self.retry_decision = Some(Box::new(decision));
         self
     }
 
     /// Adds a key-value pair to the error context.
+    ///
+    /// Pairs beyond the [`set_max_context`] cap are dropped.
     #[inline]
     #[must_use]
     pub fn with_context(
@@ -268,31 +887,424 @@ impl Error {
         value: impl Into<Cow<'static, str>>,
     ) -> Self {
         let mut v = self.context();
-        v.push((key.into(), value.into()));
-        if v.len() > 1 {
-            self.context = crate::arena::commit_to_arena(&v);
-        } else {
-            let mut cv = crate::arena::ContextVec::new();
-            for p in v {
-                cv.push(p);
-            }
-            self.context = crate::arena::ContextHandle::Heap(Box::new(cv));
+        if !push_capped(&mut v, (key.into(), value.into())) {
+            return self;
         }
+        self.replace_context(v);
         self
     }
 
-    /// Adds a key-value pair where value is converted via ToString.
-    #[inline]
+    /// Sets a context pair, replacing any existing pairs with the same key.
+    ///
+    /// Unlike [`with_context`](Error::with_context), which appends, this keeps keys
+    /// unique: the pair stays at the position of the key's first occurrence.
     #[must_use]
-    pub fn with_context_value(
-        self,
+    pub fn set_context(
+        mut self,
         key: impl Into<Cow<'static, str>>,
-        value: impl ToString,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let (key, value) = (key.into(), value.into());
+        let mut v = self.context();
+        let Some(index) = v.iter().position(|(k, _)| *k == key) else {
+            return self.with_context(key, value);
+        };
+        v[index].1 = value;
+        let rest = v.split_off(index + 1);
+        v.extend(rest.into_iter().filter(|(k, _)| *k != key));
+        self.replace_context(v);
+        self
+    }
+
+    /// Adds a context pair only if no pair with `key` exists yet.
+    ///
+    /// The complement of [`set_context`](Error::set_context): the first value wins,
+    /// which suits defaults that a lower layer may already have set more precisely.
+    #[must_use]
+    pub fn with_context_once(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> Self {
+        let key = key.into();
+        if self.context().iter().any(|(k, _)| *k == key) {
+            return self;
+        }
+        self.with_context(key, value)
+    }
+
+    /// Replaces the context, storing several pairs in the arena and a single pair on the heap.
+    fn replace_context(&mut self, v: Vec<(Cow<'static, str>, Cow<'static, str>)>) {
+        self.context = match v.len() {
+            0 => crate::arena::ContextHandle::Empty,
+            1 => crate::arena::ContextHandle::Heap(Box::new(v.into_iter().collect())),
+            _ => crate::arena::commit_to_arena(&v),
+        };
+    }
+
+    /// Returns `true` if both errors are equal with their context compared as a multiset.
+    ///
+    /// Unlike `==`, context pairs may appear in any order, which suits context
+    /// collected from unordered sources. Repeated pairs must repeat equally often.
+    pub fn eq_unordered_context(&self, other: &Self) -> bool {
+        if !self.eq_except_context(other) || self.context.len() != other.context.len() {
+            return false;
+        }
+        let (mut a, mut b) = (self.context(), other.context());
+        a.sort_unstable();
+        b.sort_unstable();
+        a == b
+    }
+
+    fn eq_except_context(&self, other: &Self) -> bool {
+        self.kind == other.kind
+            && self.status == other.status
+            && self.message == other.message
+            && self.operation == other.operation
+            && self.labels == other.labels
+            && self.attempt == other.attempt
+            && self.fatal == other.fatal
+            && self.severity_override == other.severity_override
+            && self.trace == other.trace
+            && self.retry_decision == other.retry_decision
+    }
+
+    /// Removes exact-duplicate context pairs, keeping the first occurrence of each.
+    pub fn dedup_context(&mut self) {
+        let mut v = self.context();
+        let len = v.len();
+        let mut seen = std::collections::HashSet::with_capacity(len);
+        v.retain(|pair| seen.insert(pair.clone()));
+        if v.len() != len {
+            self.replace_context(v);
+        }
+    }
+
+    /// Keeps only the last value for each context key, preserving the order of retained pairs.
+    pub fn dedup_context_by_key(&mut self) {
+        let mut v = self.context();
+        let len = v.len();
+        let mut seen = std::collections::HashSet::with_capacity(len);
+        let mut keep: Vec<bool> = v
+            .iter()
+            .rev()
+            .map(|(k, _)| seen.insert(k.clone()))
+            .collect();
+        keep.reverse();
+        let mut keep = keep.into_iter();
+        v.retain(|_| keep.next().unwrap_or(true));
+        if v.len() != len {
+            self.replace_context(v);
+        }
+    }
+
+    /// Adds a fixed set of static context pairs in one step.
+    ///
+    /// The pairs stay borrowed, so no key or value string is allocated. The
+    /// existing context is copied into new shared storage, which costs two
+    /// allocations however many pairs are added. The context cap from
+    /// [`set_max_context`] still applies.
+    #[must_use]
+    pub fn with_static_context(mut self, pairs: &'static [(&'static str, &'static str)]) -> Self {
+        let mut v = self.context();
+        v.reserve(pairs.len());
+        for &(key, value) in pairs {
+            push_capped(&mut v, (Cow::Borrowed(key), Cow::Borrowed(value)));
+        }
+        if !v.is_empty() {
+            self.context = crate::arena::ContextHandle::Shared(v.into());
+        }
+        self
+    }
+
+    /// Adds a context pair whose value is a JSON document, stored compactly.
+    ///
+    /// [`Error::to_json`] escapes it like any other string;
+    /// [`Error::to_json_structured`] nests objects and arrays as JSON.
+    #[cfg(feature = "serde")]
+    #[must_use]
+    pub fn with_context_json(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: &serde_json::Value,
+    ) -> Self {
+        self.with_context(key, value.to_string())
+    }
+
+    /// Adds a context pair whose value is computed by a fallible closure.
+    ///
+    /// On `Err` a `"<error: ...>"` placeholder is stored instead, keeping error
+    /// construction itself infallible.
+    #[must_use]
+    pub fn try_with_context<F, V, E>(self, key: impl Into<Cow<'static, str>>, f: F) -> Self
+    where
+        F: FnOnce() -> Result<V, E>,
+        V: Into<Cow<'static, str>>,
+        E: fmt::Display,
+    {
+        match f() {
+            Ok(value) => self.with_context(key, value),
+            Err(err) => self.with_context(key, format!("<error: {err}>")),
+        }
+    }
+
+    /// Adds a key-value pair where value is converted via ToString.
+    #[inline]
+    #[must_use]
+    pub fn with_context_value(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl ToString,
     ) -> Self {
         self.with_context(key, Cow::Owned(value.to_string()))
     }
 
+    /// Adds a key-value pair with a signed integer value, formatted without `Display`.
+    #[inline]
+    #[must_use]
+    pub fn with_context_int(self, key: impl Into<Cow<'static, str>>, value: i64) -> Self {
+        if value < 0 {
+            let mut buf = [0; 20];
+            let digits = format_u64(&mut buf, value.unsigned_abs());
+            let mut s = String::with_capacity(digits.len() + 1);
+            s.push('-');
+            s.push_str(digits);
+            self.with_context(key, s)
+        } else {
+            self.with_context_uint(key, value as u64)
+        }
+    }
+
+    /// Adds a key-value pair with an unsigned integer value, formatted without `Display`.
+    ///
+    /// Single digits are stored as static strings and need no allocation. Larger
+    /// values are formatted on the stack and copied into one exact-size `String`,
+    /// which is the stored value itself.
+    #[inline]
+    #[must_use]
+    pub fn with_context_uint(self, key: impl Into<Cow<'static, str>>, value: u64) -> Self {
+        const DIGITS: [&str; 10] = ["0", "1", "2", "3", "4", "5", "6", "7", "8", "9"];
+        if value < 10 {
+            return self.with_context(key, DIGITS[value as usize]);
+        }
+        let mut buf = [0; 20];
+        let digits = String::from(format_u64(&mut buf, value));
+        self.with_context(key, digits)
+    }
+
+    /// Adds a key-value pair with a duration stored as whole milliseconds, e.g. `"5200"`.
+    #[inline]
+    #[must_use]
+    pub fn with_duration(self, key: impl Into<Cow<'static, str>>, d: std::time::Duration) -> Self {
+        self.with_context_uint(key, u64::try_from(d.as_millis()).unwrap_or(u64::MAX))
+    }
+
+    /// Sets the [retry hint](Error::retry_after), replacing any earlier one.
+    #[must_use]
+    pub fn with_retry_after(self, after: std::time::Duration) -> Self {
+        let millis = u64::try_from(after.as_millis()).unwrap_or(u64::MAX);
+        self.set_context(RETRY_AFTER_KEY, millis.to_string())
+    }
+
+    /// Adds a key-value pair with a timestamp stored as milliseconds since the Unix epoch.
+    ///
+    /// Times before the epoch are stored as negative values.
+    #[must_use]
+    pub fn with_timestamp_context(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        t: std::time::SystemTime,
+    ) -> Self {
+        let millis = |d: std::time::Duration| i64::try_from(d.as_millis()).unwrap_or(i64::MAX);
+        let value = match t.duration_since(std::time::UNIX_EPOCH) {
+            Ok(after) => millis(after),
+            Err(before) => -millis(before.duration()),
+        };
+        self.with_context_int(key, value)
+    }
+
+    /// Attaches the task-local [`REQUEST_ID`](crate::context::REQUEST_ID) as
+    /// `request_id` context.
+    ///
+    /// Does nothing outside a request scope, including outside any Tokio task,
+    /// or if a `request_id` pair is already present.
+    #[cfg(feature = "ambient")]
+    #[must_use]
+    pub fn inherit_request_id(self) -> Self {
+        match crate::context::REQUEST_ID.try_with(Cow::clone) {
+            Ok(id) => self.with_context_once("request_id", id),
+            Err(_) => self,
+        }
+    }
+
+    /// Appends every context pair produced by `value`.
+    #[must_use]
+    pub fn with_context_from(self, value: &impl crate::context::ErrorContext) -> Self {
+        value
+            .error_context()
+            .into_iter()
+            .fold(self, |error, (key, value)| error.with_context(key, value))
+    }
+
+    /// Sets the `http.method` and `http.path` context pairs for a failed request.
+    ///
+    /// The keys are fixed so dashboards can rely on them; existing values are replaced.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let error = Error::not_found()
+    ///     .with_http_request("GET", "/users/42")
+    ///     .with_http_response(404);
+    /// assert_eq!(error.to_string(), "not found [http.method: GET, http.path: /users/42, http.status: 404]");
+    /// ```
+    #[must_use]
+    pub fn with_http_request(self, method: &str, path: &str) -> Self {
+        self.set_context("http.method", method.to_owned())
+            .set_context("http.path", path.to_owned())
+    }
+
+    /// Sets the `http.status` context pair to the response status code.
+    #[must_use]
+    pub fn with_http_response(self, status: u16) -> Self {
+        self.set_context("http.status", status.to_string())
+    }
+
+    /// Adds a key-value pair where value is raw bytes.
+    ///
+    /// Invalid UTF-8 sequences are replaced with `U+FFFD`.
+    #[inline]
+    #[must_use]
+    pub fn with_context_bytes(self, key: impl Into<Cow<'static, str>>, value: &[u8]) -> Self {
+        self.with_context(key, String::from_utf8_lossy(value).into_owned())
+    }
+
+    /// Appends the context pairs of `other` to this error.
+    ///
+    /// Duplicate keys are kept, so both values remain visible.
+    #[must_use]
+    pub fn merge_context(mut self, other: &Error) -> Self {
+        for (key, value) in other.context() {
+            self = self.with_context(key, value);
+        }
+        self
+    }
+
+    /// Copies the operation name from `other` if this error has none.
+    #[inline]
+    #[must_use]
+    pub fn inherit_operation(mut self, other: &Error) -> Self {
+        if self.operation.is_none() {
+            self.operation = other.operation;
+            if let Some(origin) = other.labels.as_ref().and_then(|l| l.origin) {
+                self.labels.get_or_insert_with(Default::default).origin = Some(origin);
+            }
+        }
+        self
+    }
+
+    /// Panics unless this error has the given kind; meant for tests.
+    ///
+    /// The panic message names both kinds and includes the error itself.
+    #[cfg(feature = "test-helpers")]
+    #[track_caller]
+    pub fn expect_kind(&self, kind: ErrorKind) -> &Self {
+        if self.kind != kind {
+            panic!(
+                "expected error kind `{}`, got `{}`: {self}",
+                kind.to_machine_string(),
+                self.kind.to_machine_string()
+            );
+        }
+        self
+    }
+
+    /// Replaces a domain kind with its core equivalent, when one exists.
+    ///
+    /// Helps callers that only match on core kinds: `Storage(NotFound)` becomes
+    /// `NotFound`. Kinds without a core equivalent, and everything else about
+    /// the error, are kept as-is.
+    #[must_use]
+    pub fn flatten_domain_kind(self) -> Self {
+        #[cfg(feature = "storage")]
+        if let ErrorKind::Storage(kind) = &self.kind
+            && let Some(core) = kind.to_core_kind()
+        {
+            return Self { kind: core, ..self };
+        }
+        self
+    }
+
+    /// Splits this error into kind, status, message, operation and context.
+    ///
+    /// Everything else (code, attempt, source, trace, ...) is dropped. Context
+    /// held on the heap is moved out without copying.
+    pub fn into_parts(
+        self,
+    ) -> (
+        ErrorKind,
+        ErrorStatus,
+        Cow<'static, str>,
+        Option<&'static str>,
+        ContextVec,
+    ) {
+        let context = match self.context {
+            crate::arena::ContextHandle::Heap(v) => *v,
+            crate::arena::ContextHandle::Empty => ContextVec::new(),
+            ref other => other.to_vec().into_iter().collect(),
+        };
+        (
+            self.kind,
+            self.status,
+            self.message,
+            self.operation,
+            context,
+        )
+    }
+
+    /// Reassembles an error from the output of [`into_parts`](Error::into_parts).
+    ///
+    /// The context is stored as given, without the cap or key checks of
+    /// [`with_context`](Error::with_context), and ambient scopes are not applied.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::{Error, ErrorKind};
+    ///
+    /// let (_, status, message, operation, context) = Error::not_found()
+    ///     .with_context("id", "7")
+    ///     .into_parts();
+    /// let error = Error::from_parts(ErrorKind::Validation, status, message, operation, context);
+    /// assert_eq!(error.to_string(), "not found [id: 7]");
+    /// ```
+    pub fn from_parts(
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: Cow<'static, str>,
+        operation: Option<&'static str>,
+        context: ContextVec,
+    ) -> Self {
+        let mut error = Error::new_detached(kind, status, message);
+        error.operation = operation;
+        if !context.is_empty() {
+            error.context = crate::arena::ContextHandle::Heap(Box::new(context));
+        }
+        error
+    }
+
     /// Converts this error into an Exn for context-aware propagation.
+    ///
+    /// `?` also promotes an `Error` into an `erract::Result` through exn's
+    /// `From<E> for Exn<E>`, producing the same single-frame tree. That impl is
+    /// `#[track_caller]`, so the `?` site is recorded just as accurately;
+    /// calling `raise` only keeps the conversion point explicit.
+    ///
+    /// Since neither path loses the location, `?` adds no `raised_via` marker.
+    /// The `raise_vs_from` bench group shows both paths make the same
+    /// allocations and take the same time.
     #[track_caller]
     pub fn raise(self) -> exn::Exn<Self>
     where
@@ -300,23 +1312,179 @@ impl Error {
     {
         exn::Exn::new(self)
     }
+
+    /// Returns the heap bytes this error holds, for budgeting buffered errors.
+    ///
+    /// Counts the owned message, the context storage and its owned strings, and
+    /// the boxed optional fields. Shared payloads such as the source are not counted.
+    pub fn heap_size(&self) -> usize {
+        use std::mem::size_of;
+
+        let mut size = match &self.message {
+            Cow::Owned(message) => message.capacity(),
+            Cow::Borrowed(_) => 0,
+        };
+        size += self.context.heap_size();
+        if let Some(labels) = &self.labels {
+            size += size_of::<Labels>();
+            if let Some(Cow::Owned(code)) = &labels.code {
+                size += code.capacity();
+            }
+        }
+        if let Some(attachments) = &self.attachments {
+            size += size_of::<Attachments>()
+                + attachments.details.capacity() * size_of::<Arc<dyn Any + Send + Sync>>();
+        }
+        if self.trace.is_some() {
+            size += size_of::<TraceContext>();
+        }
+        if let Some(decision) = &self.retry_decision {
+            size += size_of::<RetryDecision>();
+            if let RetryDecision::GaveUp {
+                reason: Cow::Owned(reason),
+            } = &**decision
+            {
+                size += reason.capacity();
+            }
+        }
+        size
+    }
+
+    /// Clones this error, moving its context into reference-counted storage.
+    ///
+    /// Further clones of the result share the context instead of copying it,
+    /// and the context stays readable after the clone moves to another thread.
+    pub fn clone_shallow(&self) -> Error {
+        use crate::arena::ContextHandle;
+
+        // Copy the pairs straight into the shared slice rather than cloning
+        // the context first.
+        let context = match &self.context {
+            ContextHandle::Heap(v) => ContextHandle::Shared(v.as_slice().into()),
+            handle @ ContextHandle::Arena { .. } => match handle.to_vec() {
+                v if v.is_empty() => ContextHandle::Empty,
+                v => ContextHandle::Shared(v.into()),
+            },
+            ContextHandle::Shared(v) => ContextHandle::Shared(Arc::clone(v)),
+            ContextHandle::Empty => ContextHandle::Empty,
+        };
+        Error {
+            kind: self.kind.clone(),
+            status: self.status.clone(),
+            message: self.message.clone(),
+            operation: self.operation,
+            labels: self.labels.clone(),
+            context,
+            attachments: self.attachments.clone(),
+            attempt: self.attempt,
+            fatal: self.fatal,
+            severity_override: self.severity_override,
+            trace: self.trace.clone(),
+            retry_decision: self.retry_decision.clone(),
+        }
+    }
+
+    /// Returns a copy of this error that is safe to expose to clients.
+    ///
+    /// Keeps the kind, status, code and message ID but replaces the message with the
+    /// generic [`ErrorKind::client_message`] and drops the operation, context, source
+    /// and details.
+    pub fn sanitize_for_client(&self) -> Error {
+        let mut error = Error::new_detached(
+            self.kind.clone(),
+            self.status.clone(),
+            self.kind.client_message(),
+        );
+        if let Some(labels) = &self.labels {
+            error.labels = Some(Box::new(Labels {
+                origin: None,
+                context_frame: false,
+                ..(**labels).clone()
+            }));
+        }
+        error
+    }
+
+    /// Adds a context pair and raises the error in one step.
+    #[track_caller]
+    pub fn raise_with(
+        self,
+        key: impl Into<Cow<'static, str>>,
+        value: impl Into<Cow<'static, str>>,
+    ) -> exn::Exn<Self> {
+        exn::Exn::new(self.with_context(key, value))
+    }
+
+    /// Adds multiple context pairs and raises the error in one step.
+    #[track_caller]
+    pub fn raise_with_iter<K, V>(self, iter: impl IntoIterator<Item = (K, V)>) -> exn::Exn<Self>
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        let mut error = self;
+        for (key, value) in iter {
+            error = error.with_context(key, value);
+        }
+        exn::Exn::new(error)
+    }
+
+    /// Converts this error into a [`BoxError`].
+    #[inline]
+    pub fn boxed(self) -> BoxError {
+        Box::new(self)
+    }
 }
 
 impl fmt::Display for Error {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        // Bare errors are the common case; skip the context lookup entirely.
+        if self.operation.is_none() && self.context.is_empty() {
+            return f.write_str(&self.message);
+        }
+        self.fmt_with(f, &DisplayOptions::default())
+    }
+}
+
+impl Error {
+    /// Returns a wrapper that renders this error on one line using `opts`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::{DisplayOptions, Error};
+    ///
+    /// let error = Error::not_found().with_context("a", "1").with_context("b", "2");
+    /// let opts = DisplayOptions::new().with_separator("; ");
+    /// assert_eq!(error.display_with(opts).to_string(), "not found [a: 1; b: 2]");
+    /// ```
+    #[inline]
+    pub fn display_with(&self, opts: DisplayOptions) -> ErrorDisplay<'_> {
+        ErrorDisplay { error: self, opts }
+    }
+
+    fn fmt_with(&self, f: &mut fmt::Formatter<'_>, opts: &DisplayOptions) -> fmt::Result {
         f.write_str(&self.message)?;
-        if let Some(op) = self.operation {
+        if opts.include_operation
+            && let Some(op) = self.operation
+        {
             f.write_str(" (operation: ")?;
             f.write_str(op)?;
             f.write_char(')')?;
         }
+        if !opts.include_context || self.context.is_empty() {
+            return Ok(());
+        }
         let context = self.context();
         if !context.is_empty() {
-            f.write_str(" [")?;
+            if !self.message.is_empty() {
+                f.write_char(' ')?;
+            }
+            f.write_char('[')?;
             let mut first = true;
             for (key, value) in &context {
                 if !first {
-                    f.write_str(", ")?;
+                    f.write_str(opts.separator)?;
                 }
                 f.write_str(key)?;
                 f.write_str(": ")?;
@@ -329,9 +1497,109 @@ impl fmt::Display for Error {
     }
 }
 
+/// Controls how [`Error::display_with`] renders an error.
+///
+/// The default matches the `Display` impl: operation included, context pairs
+/// separated by `", "`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DisplayOptions {
+    separator: &'static str,
+    include_operation: bool,
+    include_context: bool,
+}
+
+impl Default for DisplayOptions {
+    fn default() -> Self {
+        Self {
+            separator: ", ",
+            include_operation: true,
+            include_context: true,
+        }
+    }
+}
+
+impl DisplayOptions {
+    /// Creates options matching the default `Display` output.
+    #[inline]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the delimiter written between context pairs.
+    #[inline]
+    #[must_use]
+    pub fn with_separator(mut self, separator: &'static str) -> Self {
+        self.separator = separator;
+        self
+    }
+
+    /// Sets whether the operation is rendered.
+    #[inline]
+    #[must_use]
+    pub fn with_operation(mut self, include: bool) -> Self {
+        self.include_operation = include;
+        self
+    }
+
+    /// Sets whether the context pairs are rendered.
+    #[inline]
+    #[must_use]
+    pub fn with_context(mut self, include: bool) -> Self {
+        self.include_context = include;
+        self
+    }
+}
+
+/// A structured view of an [`Error`]'s fields; see [`Error::fields`].
+///
+/// The context is copied out, since arena-backed context is not stored as pairs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ErrorFields<'a> {
+    /// The error kind.
+    pub kind: &'a ErrorKind,
+    /// The retry status.
+    pub status: &'a ErrorStatus,
+    /// The error message.
+    pub message: &'a str,
+    /// The operation that failed, if set.
+    pub operation: Option<&'a str>,
+    /// Whether the error is safe to retry.
+    pub retryable: bool,
+    /// The context key-value pairs.
+    pub context: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl<'a> From<&'a Error> for ErrorFields<'a> {
+    fn from(error: &'a Error) -> Self {
+        Self {
+            kind: &error.kind,
+            status: &error.status,
+            message: &error.message,
+            operation: error.operation,
+            retryable: error.is_retryable(),
+            context: error.context(),
+        }
+    }
+}
+
+/// Renders an [`Error`] with custom [`DisplayOptions`]; see [`Error::display_with`].
+#[derive(Debug, Clone, Copy)]
+pub struct ErrorDisplay<'a> {
+    error: &'a Error,
+    opts: DisplayOptions,
+}
+
+impl fmt::Display for ErrorDisplay<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt_with(f, &self.opts)
+    }
+}
+
 impl std::error::Error for Error {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
-        self.source
+        self.attachments
+            .as_deref()?
+            .source
             .as_deref()
             .map(|e| e as &(dyn std::error::Error + 'static))
     }
@@ -343,137 +1611,317 @@ impl Default for Error {
     }
 }
 
-impl Error {
-    /// Returns a machine-readable string representation of this error.
-    pub fn to_machine_string(&self) -> String {
-        let context = self.context();
-        let capacity = 64 + self.message.len() + context.len() * 32;
-        let mut output = String::with_capacity(capacity);
+/// Wrapper that lets an `Exn<Error>` cross a [`BoxError`] boundary.
+///
+/// The `Display` output renders every frame in the tree, outermost first,
+/// joined with `": "`.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{BoxError, Error, ExnError};
+///
+/// let exn = Error::not_found().raise();
+/// let boxed: BoxError = ExnError::from(exn).into();
+/// assert_eq!(boxed.to_string(), "not found");
+/// ```
+pub struct ExnError(exn::Exn<Error>);
+
+impl ExnError {
+    /// Returns the wrapped error tree.
+    #[inline]
+    pub fn into_inner(self) -> exn::Exn<Error> {
+        self.0
+    }
+}
 
-        output.push_str("kind=");
-        output.push_str(&self.kind.to_machine_string());
-        output.push_str(";status=");
-        output.push_str(self.status.to_machine_string());
-        output.push_str(";message=");
-        output.push_str(&self.message);
+impl From<exn::Exn<Error>> for ExnError {
+    #[inline]
+    fn from(exn: exn::Exn<Error>) -> Self {
+        ExnError(exn)
+    }
+}
 
-        if let Some(op) = self.operation {
-            output.push_str(";operation=");
-            output.push_str(op);
-        }
+impl fmt::Debug for ExnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.0, f)
+    }
+}
 
-        if !context.is_empty() {
-            output.push_str(";context=[");
-            let mut first = true;
-            for (k, v) in &context {
-                if !first {
-                    output.push(',');
-                }
-                output.push_str(k);
-                output.push('=');
-                output.push_str(v);
-                first = false;
-            }
-            output.push(']');
-        }
+impl fmt::Display for ExnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        crate::extract::write_chain(f, self.0.as_frame())
+    }
+}
 
+impl std::error::Error for ExnError {}
+
+impl Error {
+    /// Returns a machine-readable string representation of this error.
+    pub fn to_machine_string(&self) -> String {
+        let capacity = 64 + self.message.len() + self.context.len() * 32;
+        let mut output = String::with_capacity(capacity);
+        let mut visitor = MachineVisitor::new(&mut output);
+        self.visit(&mut visitor);
+        visitor.finish();
         output
     }
 
     /// Returns a JSON representation of this error.
     pub fn to_json(&self) -> String {
-        let context = self.context();
-        let capacity = 128 + self.message.len() + context.len() * 48;
+        let capacity = 128 + self.message.len() + self.context.len() * 48;
         let mut json = String::with_capacity(capacity);
+        self.write_json(&mut json);
+        json
+    }
 
-        json.push_str(r#"{"kind":""#);
-        json.push_str(&self.kind.to_machine_string());
-        json.push_str(r#"","status":""#);
-        json.push_str(self.status.to_machine_string());
-        json.push_str(r#"","message":""#);
+    /// Returns a JSON representation where context values holding a JSON object
+    /// or array are nested as JSON instead of being quoted.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let limits = serde_json::json!({"max": 10});
+    /// let error = Error::not_found().with_context_json("limits", &limits);
+    /// assert!(error.to_json().contains(r#""limits":"{\"max\":10}""#));
+    /// assert!(error.to_json_structured().contains(r#""limits":{"max":10}"#));
+    /// ```
+    #[cfg(feature = "serde")]
+    pub fn to_json_structured(&self) -> String {
+        let mut json = String::with_capacity(128 + self.message.len() + self.context.len() * 48);
+        let mut visitor = JsonVisitor::new(&mut json, None).inline_json();
+        self.visit(&mut visitor);
+        visitor.finish();
+        json
+    }
+
+    /// Returns an indented JSON representation of this error, for human consumption.
+    pub fn to_json_pretty(&self) -> String {
+        let mut json = String::with_capacity(192 + self.message.len() + self.context.len() * 56);
+        self.write_json_pretty(&mut json, 2);
+        json
+    }
+
+    /// Returns an RFC 7807 "Problem Details" JSON object for this error.
+    ///
+    /// `type` is `urn:erract:<kind machine string>`, `title` the kind's `Display`,
+    /// `status` its [`http_status`](ErrorKind::http_status) and `detail` the message.
+    /// Context pairs become extension members in key order, except those that
+    /// would shadow a standard member. A repeated key keeps its last value, so
+    /// member names stay unique.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let json = Error::not_found().to_problem_json(Some("/users/7"));
+    /// assert!(json.starts_with(r#"{"type":"urn:erract:not_found","title":"not found","status":404"#));
+    /// ```
+    pub fn to_problem_json(&self, instance: Option<&str>) -> String {
+        const MEMBERS: [&str; 5] = ["type", "title", "status", "detail", "instance"];
+
+        let mut json = String::with_capacity(160 + self.message.len() + self.context.len() * 48);
+        json.push_str(r#"{"type":"urn:erract:"#);
+        write_escaped(&mut json, &self.kind.to_machine_string());
+        json.push_str(r#"","title":""#);
+        write_escaped(&mut json, &self.kind.to_string());
+        let _ = write!(json, r#"","status":{},"detail":""#, self.kind.http_status());
         write_escaped(&mut json, &self.message);
         json.push('"');
-
-        if let Some(op) = self.operation {
-            json.push_str(r#","operation":""#);
-            json.push_str(op);
+        if let Some(instance) = instance {
+            json.push_str(r#","instance":""#);
+            write_escaped(&mut json, instance);
             json.push('"');
         }
-
-        if !context.is_empty() {
-            json.push_str(r#","context":{"#);
-            let mut first = true;
-            for (k, v) in &context {
-                if !first {
-                    json.push(',');
-                }
-                json.push('"');
-                write_escaped(&mut json, k);
-                json.push_str(r#"":""#);
-                write_escaped(&mut json, v);
-                json.push('"');
-                first = false;
+        for (k, v) in &self.context_map() {
+            if MEMBERS.contains(&k.as_str()) {
+                continue;
             }
-            json.push('}');
+            json.push_str(r#",""#);
+            write_escaped(&mut json, k);
+            json.push_str(r#"":""#);
+            write_escaped(&mut json, v);
+            json.push('"');
         }
-
         json.push('}');
         json
     }
 
+    /// Writes indented JSON to the provided buffer, using `indent` spaces per level.
+    pub fn write_json_pretty(&self, buf: &mut String, indent: usize) {
+        let mut visitor = JsonVisitor::new(buf, Some(indent));
+        self.visit(&mut visitor);
+        visitor.finish();
+    }
+
+    /// Returns the [`to_json`](Error::to_json) output as UTF-8 bytes.
+    pub fn to_json_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(128 + self.message.len() + self.context.len() * 48);
+        self.write_json_bytes(&mut bytes);
+        bytes
+    }
+
+    /// Appends the [`to_json`](Error::to_json) output as UTF-8 to a byte buffer,
+    /// without going through an intermediate `String`.
+    pub fn write_json_bytes(&self, buf: &mut Vec<u8>) {
+        let mut buf = ByteBuf(buf);
+        let mut visitor = JsonVisitor::new(&mut buf, None);
+        self.visit(&mut visitor);
+        visitor.finish();
+    }
+
     /// Writes JSON to the provided buffer, avoiding allocation.
     pub fn write_json(&self, buf: &mut String) {
-        let context = self.context();
-        buf.push_str(r#"{"kind":""#);
-        buf.push_str(&self.kind.to_machine_string());
-        buf.push_str(r#"","status":""#);
-        buf.push_str(self.status.to_machine_string());
-        buf.push_str(r#"","message":""#);
-        write_escaped(buf, &self.message);
-        buf.push('"');
+        let mut visitor = JsonVisitor::new(buf, None);
+        self.visit(&mut visitor);
+        visitor.finish();
+    }
 
+    /// Flattens the error into key-value pairs for loggers that only take pairs.
+    ///
+    /// The reserved keys `error.kind` and `error.status` (machine strings),
+    /// `error.message` and, when set, `error.operation` come first, followed by
+    /// the context pairs in insertion order.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let kv = Error::not_found().with_context("user_id", "42").to_kv();
+    /// assert_eq!(kv[0], ("error.kind".into(), "not_found".into()));
+    /// assert_eq!(kv[3], ("user_id".into(), "42".into()));
+    /// ```
+    pub fn to_kv(&self) -> Vec<(Cow<'static, str>, Cow<'static, str>)> {
+        let context = self.context();
+        let mut kv = Vec::with_capacity(4 + context.len());
+        kv.push((Cow::Borrowed("error.kind"), self.kind.to_machine_string()));
+        kv.push((
+            Cow::Borrowed("error.status"),
+            Cow::Borrowed(self.status.to_machine_string()),
+        ));
+        kv.push((Cow::Borrowed("error.message"), self.message.clone()));
         if let Some(op) = self.operation {
-            buf.push_str(r#","operation":""#);
-            buf.push_str(op);
-            buf.push('"');
+            kv.push((Cow::Borrowed("error.operation"), Cow::Borrowed(op)));
         }
+        kv.extend(context);
+        kv
+    }
 
-        if !context.is_empty() {
-            buf.push_str(r#","context":{"#);
-            let mut first = true;
-            for (k, v) in &context {
-                if !first {
-                    buf.push(',');
-                }
-                buf.push('"');
-                write_escaped(buf, k);
-                buf.push_str(r#"":""#);
-                write_escaped(buf, v);
-                buf.push('"');
-                first = false;
-            }
-            buf.push('}');
-        }
+    /// Returns a JSON representation with context pairs as top-level members.
+    ///
+    /// Suits log schemas such as ECS that expect flat fields. Context keys that
+    /// collide with a top-level field such as `kind` or `message` are written as
+    /// `context.<key>`. [`to_json`](Error::to_json) keeps context nested.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let error = Error::not_found().with_context("user_id", "123").with_context("message", "raw");
+    /// assert_eq!(
+    ///     error.to_json_flat(),
+    ///     r#"{"kind":"not_found","status":"permanent","message":"not found","user_id":"123","context.message":"raw"}"#
+    /// );
+    /// ```
+    pub fn to_json_flat(&self) -> String {
+        let mut json = String::with_capacity(128 + self.message.len() + self.context.len() * 48);
+        let mut visitor = JsonVisitor::new(&mut json, None).flat();
+        self.visit(&mut visitor);
+        visitor.finish();
+        json
+    }
 
-        buf.push('}');
+    /// Returns a JSON representation with context keys in sorted order.
+    ///
+    /// Unlike [`to_json`](Error::to_json), which keeps insertion order, the output
+    /// is reproducible when context comes from an unordered source, which suits
+    /// snapshot tests and cache keys.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::Error;
+    ///
+    /// let error = Error::not_found().with_context("b", "2").with_context("a", "1");
+    /// assert!(error.to_json_sorted().ends_with(r#""context":{"a":"1","b":"2"}}"#));
+    /// ```
+    pub fn to_json_sorted(&self) -> String {
+        let mut json = String::with_capacity(128 + self.message.len() + self.context.len() * 48);
+        self.write_json_sorted(&mut json);
+        json
+    }
+
+    /// Writes the [`to_json_sorted`](Error::to_json_sorted) output to the provided buffer.
+    pub fn write_json_sorted(&self, buf: &mut String) {
+        let mut visitor = JsonVisitor::new(buf, None);
+        self.visit_sorted(&mut visitor);
+        visitor.finish();
     }
 }
 
-#[inline]
-fn write_escaped(buf: &mut String, s: &str) {
-    for c in s.chars() {
-        match c {
-            '"' => buf.push_str(r#"\""#),
-            '\\' => buf.push_str(r#"\\"#),
-            '\n' => buf.push_str(r#"\n"#),
-            '\r' => buf.push_str(r#"\r"#),
-            '\t' => buf.push_str(r#"\t"#),
-            c if c.is_control() => {
-                let _ = write!(buf, r#"\u{:04x}"#, c as u32);
-            }
-            c => buf.push(c),
+/// Writes the decimal digits of `n` to the end of `buf` and returns them.
+fn format_u64(buf: &mut [u8; 20], mut n: u64) -> &str {
+    let mut start = buf.len();
+    loop {
+        start -= 1;
+        buf[start] = b'0' + (n % 10) as u8;
+        n /= 10;
+        if n == 0 {
+            break;
+        }
+    }
+    std::str::from_utf8(&buf[start..]).unwrap_or_default()
+}
+
+/// Pushes `pair`, or the truncation marker once the context cap is reached.
+///
+/// Keys with control characters go through the [`KeySanitizer`] first and values
+/// longer than [`max_context_value_len`] are truncated.
+/// Returns `false` if nothing was pushed, because the key is blank or rejected,
+/// or the context was already truncated.
+fn push_capped(
+    v: &mut Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    mut pair: (Cow<'static, str>, Cow<'static, str>),
+) -> bool {
+    if pair.0.contains(char::is_control) {
+        let sanitize = *KEY_SANITIZER.read().unwrap_or_else(|e| e.into_inner());
+        match sanitize(&pair.0) {
+            Some(key) => pair.0 = Cow::Owned(key),
+            None => return false,
+        }
+    }
+    let max_len = max_context_value_len();
+    // A value can only exceed `max_len` characters if it has more bytes than that.
+    if pair.1.len() > max_len
+        && let Some((cut, _)) = pair.1.char_indices().nth(max_len)
+    {
+        let mut value = String::with_capacity(cut + TRUNCATED_VALUE_SUFFIX.len());
+        value.push_str(&pair.1[..cut]);
+        value.push_str(TRUNCATED_VALUE_SUFFIX);
+        pair.1 = Cow::Owned(value);
+    }
+    if pair.0.trim().is_empty() {
+        debug_assert!(
+            !STRICT_CONTEXT_KEYS.load(Ordering::Relaxed),
+            "empty context key (value: {:?})",
+            pair.1
+        );
+        return false;
+    }
+    if v.len() >= max_context() {
+        if v.last().is_some_and(|(k, _)| k == TRUNCATED_KEY) {
+            return false;
         }
+        v.push((Cow::Borrowed(TRUNCATED_KEY), Cow::Borrowed("true")));
+    } else {
+        v.push(pair);
     }
+    true
 }
 
 mod builder {
@@ -497,14 +1945,7 @@ mod builder {
             message: impl Into<Cow<'static, str>>,
         ) -> Self {
             ErrorBuilder {
-                error: Error {
-                    kind,
-                    status,
-                    message: message.into(),
-                    operation: None,
-                    context: crate::arena::ContextHandle::Empty,
-                    source: None,
-                },
+                error: Error::new(kind, status, message),
             }
         }
 
@@ -512,7 +1953,15 @@ mod builder {
         #[inline]
         #[must_use]
         pub fn with_operation(mut self, operation: &'static str) -> Self {
-            self.error.operation = Some(operation);
+            self.error = self.error.with_operation(operation);
+            self
+        }
+
+        /// Sets an application-specific error code.
+        #[inline]
+        #[must_use]
+        pub fn with_code(mut self, code: impl Into<Cow<'static, str>>) -> Self {
+            self.error = self.error.with_code(code);
             self
         }
 
@@ -523,7 +1972,10 @@ mod builder {
         where
             E: std::error::Error + Send + Sync + 'static,
         {
-            self.error.source = Some(Arc::new(source));
+            self.error
+                .attachments
+                .get_or_insert_with(Default::default)
+                .source = Some(Arc::new(source));
             self
         }
 
@@ -551,6 +2003,22 @@ mod builder {
             self
         }
 
+        /// Adds a context key-value pair with a signed integer value.
+        #[inline]
+        #[must_use]
+        pub fn with_context_int(mut self, key: impl Into<Cow<'static, str>>, value: i64) -> Self {
+            self.error = self.error.with_context_int(key, value);
+            self
+        }
+
+        /// Adds a context key-value pair with an unsigned integer value.
+        #[inline]
+        #[must_use]
+        pub fn with_context_uint(mut self, key: impl Into<Cow<'static, str>>, value: u64) -> Self {
+            self.error = self.error.with_context_uint(key, value);
+            self
+        }
+
         /// Adds multiple context key-value pairs.
         #[inline]
         #[must_use]
@@ -599,6 +2067,1062 @@ mod tests {
         assert_eq!(error.context().len(), 2);
     }
 
+    #[test]
+    fn test_context_map() {
+        let error = Error::not_found()
+            .with_context("id", "1")
+            .with_context("region", "eu")
+            .with_context("id", "2");
+
+        let map = error.context_map();
+        assert_eq!(map.len(), 2);
+        assert_eq!(map["id"], "2");
+
+        let multi = error.context_map_multi();
+        assert_eq!(multi["id"], ["1", "2"]);
+        assert_eq!(multi["region"], ["eu"]);
+    }
+
+    #[test]
+    fn test_eq_unordered_context() {
+        let a = Error::timeout()
+            .with_context("region", "eu")
+            .with_context("host", String::from("db-1"))
+            .with_context("host", "db-2");
+        let b = Error::timeout()
+            .with_context("host", "db-2")
+            .with_context(String::from("region"), "eu")
+            .with_context("host", "db-1");
+        assert!(a.eq_unordered_context(&b));
+        assert!(b.eq_unordered_context(&a));
+        assert_ne!(a, b);
+
+        let missing_dup = Error::timeout()
+            .with_context("region", "eu")
+            .with_context("host", "db-1")
+            .with_context("host", "db-1");
+        assert!(!a.eq_unordered_context(&missing_dup));
+        assert!(!a.eq_unordered_context(&a.clone().with_operation("fetch")));
+        assert!(Error::timeout().eq_unordered_context(&Error::timeout()));
+    }
+
+    #[test]
+    fn test_eq_kind_and_status() {
+        let error = Error::timeout();
+        assert_eq!(error, ErrorKind::Timeout);
+        assert_ne!(error, ErrorKind::NotFound);
+        assert_eq!(error, ErrorStatus::Temporary);
+        assert_ne!(error, ErrorStatus::Permanent);
+    }
+
+    #[test]
+    fn test_merge_context() {
+        let first = Error::not_found().with_context("user_id", "1");
+        let second = Error::timeout()
+            .with_operation("fetch")
+            .with_context("user_id", "2")
+            .with_context("region", "eu");
+
+        let merged = Error::unexpected()
+            .merge_context(&first)
+            .merge_context(&second)
+            .inherit_operation(&second);
+
+        assert_eq!(merged.context().len(), 3);
+        assert_eq!(merged.context()[1].1, "2");
+        assert_eq!(merged.operation(), Some("fetch"));
+    }
+
+    #[test]
+    fn test_context_bytes_lossy() {
+        let error = Error::not_found().with_context_bytes("header", b"ab\xffcd");
+        assert_eq!(error.context()[0].1, "ab\u{fffd}cd");
+    }
+
+    #[test]
+    fn test_sanitize_for_client() {
+        let error = Error::permanent(ErrorKind::NotFound, "row 42 missing in users_v2")
+            .with_operation("load_user")
+            .with_context("table", "users_v2");
+        let sanitized = error.sanitize_for_client();
+
+        assert_eq!(sanitized.kind(), &ErrorKind::NotFound);
+        assert!(sanitized.is_permanent());
+        assert_eq!(sanitized.message(), "The requested resource was not found.");
+        assert_eq!(sanitized.operation(), None);
+        assert!(sanitized.context().is_empty());
+    }
+
+    #[test]
+    fn test_boxed() {
+        let boxed = Error::not_found().with_context("id", "7").boxed();
+        assert_eq!(boxed.to_string(), "not found [id: 7]");
+        assert!(boxed.downcast_ref::<Error>().is_some());
+    }
+
+    #[test]
+    fn test_exn_error_displays_tree() {
+        use exn::ResultExt;
+
+        let result: crate::Result<()> = Err(Error::not_found().raise());
+        let exn = result
+            .or_raise(|| Error::temporary(ErrorKind::Unexpected, "outer"))
+            .unwrap_err();
+        let boxed: BoxError = ExnError::from(exn).into();
+        assert_eq!(boxed.to_string(), "outer: not found");
+    }
+
+    #[test]
+    fn test_question_mark_promotes_to_exn() {
+        let line = line!() + 2;
+        fn fail() -> crate::Result<()> {
+            Err(Error::not_found())?
+        }
+
+        let exn = fail().unwrap_err();
+        assert_eq!(exn.as_error().kind(), &ErrorKind::NotFound);
+        assert_eq!(crate::count_frames(&exn), 1);
+        assert!(exn.as_frame().location().file().ends_with("error.rs"));
+        // exn's `From` impl is `#[track_caller]`, so `?` records its own line and
+        // needs no `raised_via` marker.
+        assert!(exn.as_error().context().is_empty());
+        assert_eq!(exn.as_frame().location().line(), line);
+    }
+
+    #[test]
+    fn test_from_and_raise_record_same_location_shape() {
+        let via_from: exn::Exn<Error> = Error::not_found().into();
+        let line = line!() - 1;
+        let via_raise = Error::not_found().raise();
+        assert_eq!(via_from.as_frame().location().line(), line);
+        assert_eq!(via_raise.as_frame().location().line(), line + 2);
+        assert_eq!(via_from.as_error(), via_raise.as_error());
+    }
+
+    #[test]
+    fn test_raise_with() {
+        let line = line!() + 1;
+        let exn = Error::not_found().raise_with("user_id", "42");
+        assert_eq!(exn.as_error().context()[0].1, "42");
+        assert_eq!(exn.as_frame().location().line(), line);
+
+        let exn = Error::not_found().raise_with_iter([("a", "1"), ("b", "2")]);
+        assert_eq!(exn.as_error().context().len(), 2);
+    }
+
+    #[test]
+    fn test_attempt_in_json() {
+        let error = Error::timeout().with_attempt(3);
+        assert_eq!(error.attempt(), Some(3));
+        assert!(error.to_json().contains(r#""attempt":3"#));
+        assert!(!Error::timeout().to_json().contains("attempt"));
+    }
+
+    #[test]
+    fn test_fatal() {
+        let error = Error::fatal(ErrorKind::Unexpected, "index corrupted");
+        assert!(error.is_fatal());
+        assert!(error.is_permanent());
+        assert!(error.to_json().contains(r#""fatal":true"#));
+
+        assert!(!Error::unexpected().is_fatal());
+        assert!(Error::unexpected().mark_fatal().is_fatal());
+    }
+
+    #[test]
+    fn test_to_json_pretty() {
+        let error = Error::not_found()
+            .with_operation("fetch")
+            .with_context("user", "a\"b");
+        let json = error.to_json_pretty();
+
+        assert!(json.contains("\n  \"kind\": \"not_found\""));
+        assert!(json.contains("\n    \"user\": \"a\\\"b\""));
+        assert!(json.ends_with("\n  }\n}"));
+
+        let parsed: serde_json::Value = serde_json::from_str(&json).unwrap();
+        let compact: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(parsed, compact);
+    }
+
+    #[test]
+    fn test_trace_context_in_json() {
+        let mut trace_id = [0u8; 16];
+        trace_id[15] = 0xab;
+        let error =
+            Error::unexpected().with_trace_context(trace_id, [0, 0, 0, 0, 0, 0, 0x01, 0xff]);
+
+        let json = error.to_json();
+        assert!(json.contains(r#""trace_id":"000000000000000000000000000000ab""#));
+        assert!(json.contains(r#""span_id":"00000000000001ff""#));
+
+        let error = Error::unexpected().with_trace_context([0; 16], [0; 8]);
+        assert!(error.trace_context().is_none());
+        assert!(!error.to_json().contains("trace_id"));
+    }
+
+    #[cfg(feature = "otel")]
+    #[test]
+    fn test_with_current_trace() {
+        use opentelemetry::trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState,
+        };
+
+        assert!(
+            Error::unexpected()
+                .with_current_trace()
+                .trace_context()
+                .is_none()
+        );
+
+        let span_context = SpanContext::new(
+            TraceId::from_bytes([1; 16]),
+            SpanId::from_bytes([2; 8]),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let _guard = opentelemetry::Context::current()
+            .with_remote_span_context(span_context)
+            .attach();
+
+        let error = Error::unexpected().with_current_trace();
+        let trace = error.trace_context().unwrap();
+        assert_eq!(trace.trace_id, [1; 16]);
+        assert_eq!(trace.span_id, [2; 8]);
+    }
+
+    #[test]
+    fn test_with_detail() {
+        #[derive(Debug, PartialEq)]
+        struct QuotaFailure {
+            subject: &'static str,
+            limit: u32,
+        }
+
+        let error = Error::temporary(ErrorKind::Unexpected, "quota exceeded")
+            .with_detail(QuotaFailure {
+                subject: "project:42",
+                limit: 100,
+            })
+            .with_detail(7u32);
+
+        let exn = error.raise();
+        let detail = exn.as_error().detail::<QuotaFailure>().unwrap();
+        assert_eq!(detail.subject, "project:42");
+        assert_eq!(detail.limit, 100);
+        assert_eq!(exn.as_error().clone().detail::<u32>(), Some(&7));
+        assert_eq!(exn.as_error().detail::<String>(), None);
+        assert_eq!(Error::not_found().detail::<u32>(), None);
+    }
+
+    #[test]
+    fn test_should_log() {
+        let warning = Error::not_found();
+        assert!(warning.should_log(Severity::Info));
+        assert!(warning.should_log(Severity::Warning));
+        assert!(!warning.should_log(Severity::Error));
+        assert!(Error::unexpected().should_log(Severity::Error));
+        assert_eq!(min_log_severity(), Severity::Info);
+    }
+
+    #[test]
+    fn test_occurrence_count_escalates_severity() {
+        let error = Error::not_found();
+        assert_eq!(error.occurrence_count(), None);
+        assert_eq!(error.effective_severity(), Severity::Warning);
+
+        let error = error.with_occurrence_count(999);
+        assert_eq!(error.effective_severity(), Severity::Warning);
+        assert!(!error.should_log(Severity::Error));
+
+        let error = error.with_occurrence_count(1_000);
+        assert_eq!(error.occurrence_count(), Some(1_000));
+        assert_eq!(error.kind().severity(), Severity::Warning);
+        assert_eq!(error.effective_severity(), Severity::Error);
+        assert!(error.should_log(Severity::Error));
+
+        let storm = Error::unexpected().with_occurrence_count(u64::MAX);
+        assert_eq!(storm.effective_severity(), Severity::Critical);
+    }
+
+    #[test]
+    fn test_severity_score_override() {
+        let error = Error::permanent(ErrorKind::NotFound, "missing");
+        assert_eq!(error.severity_score(), 40);
+
+        let error = error.with_severity_override(85);
+        assert_eq!(error.severity_score(), 85);
+        assert_eq!(error.kind().severity_score(), 40);
+        assert_ne!(error, Error::permanent(ErrorKind::NotFound, "missing"));
+
+        assert_eq!(
+            Error::unexpected()
+                .with_severity_override(250)
+                .severity_score(),
+            100
+        );
+    }
+
+    #[test]
+    fn test_severity_cmp() {
+        use std::cmp::Ordering;
+
+        let warning = Error::permanent(ErrorKind::NotFound, "missing");
+        let error = Error::temporary(ErrorKind::Unexpected, "boom");
+        assert_eq!(warning.severity_cmp(&error), Ordering::Less);
+        assert_eq!(error.severity_cmp(&warning), Ordering::Greater);
+
+        let temporary = Error::temporary(ErrorKind::Timeout, "slow");
+        let persistent = Error::persistent(ErrorKind::Timeout, "still slow");
+        assert_eq!(temporary.severity_cmp(&persistent), Ordering::Less);
+        assert_eq!(persistent.severity_cmp(&warning), Ordering::Less);
+        assert_eq!(warning.severity_cmp(&warning.clone()), Ordering::Equal);
+    }
+
+    #[test]
+    fn test_message_only() {
+        let error = Error::permanent(ErrorKind::NotFound, "user not found");
+        assert_eq!(error.message_only(), error.to_string());
+
+        let error = error.with_operation("lookup").with_context("id", "1");
+        assert_eq!(error.message_only(), "user not found");
+        assert_ne!(error.message_only(), error.to_string());
+    }
+
+    #[test]
+    fn test_try_with_context() {
+        let error = Error::not_found()
+            .try_with_context("ok", || Ok::<_, fmt::Error>("fine"))
+            .try_with_context("payload", || "x".parse::<u32>().map(|n| n.to_string()));
+        let context = error.context();
+        assert_eq!(context[0], ("ok".into(), "fine".into()));
+        assert_eq!(
+            context[1],
+            (
+                "payload".into(),
+                "<error: invalid digit found in string>".into()
+            )
+        );
+    }
+
+    #[test]
+    fn test_dedup_context() {
+        let mut error = Error::not_found()
+            .with_context("request_id", "r1")
+            .with_context("user", "a")
+            .with_context("request_id", "r1")
+            .with_context("user", "b");
+        error.dedup_context();
+        let context = error.context();
+        assert_eq!(context.len(), 3);
+        assert_eq!(context[0], ("request_id".into(), "r1".into()));
+        assert_eq!(context[1], ("user".into(), "a".into()));
+        assert_eq!(context[2], ("user".into(), "b".into()));
+    }
+
+    #[test]
+    fn test_dedup_context_by_key() {
+        let mut error = Error::not_found()
+            .with_context("user", "a")
+            .with_context("request_id", "r1")
+            .with_context("user", "b")
+            .with_context("step", "2");
+        error.dedup_context_by_key();
+        let context = error.context();
+        assert_eq!(context.len(), 3);
+        assert_eq!(context[0], ("request_id".into(), "r1".into()));
+        assert_eq!(context[1], ("user".into(), "b".into()));
+        assert_eq!(context[2], ("step".into(), "2".into()));
+
+        let mut single = Error::not_found().with_context("k", "v");
+        single.dedup_context_by_key();
+        assert_eq!(single.context().len(), 1);
+    }
+
+    #[test]
+    fn test_blank_context_keys_are_skipped() {
+        let error = Error::not_found()
+            .with_context("", "lost")
+            .with_context("  ", "lost")
+            .with_context("id", "1")
+            .with_static_context(&[("", "lost"), ("tag", "x")]);
+        let context = error.context();
+        assert_eq!(context.len(), 2);
+        assert_eq!(context[0], ("id".into(), "1".into()));
+        assert_eq!(context[1], ("tag".into(), "x".into()));
+    }
+
+    #[test]
+    fn test_control_characters_stripped_from_keys() {
+        let error = Error::not_found()
+            .with_context("user\nid", "7")
+            .with_context_value("a\x1b[31mb", 1)
+            .with_context("\r\n", "lost");
+        let context = error.context();
+        assert_eq!(context.len(), 2);
+        assert_eq!(context[0], ("userid".into(), "7".into()));
+        assert_eq!(context[1], ("a[31mb".into(), "1".into()));
+        assert_eq!(error.to_string(), "not found [userid: 7, a[31mb: 1]");
+    }
+
+    #[test]
+    fn test_timeout_after() {
+        use std::time::Duration;
+
+        let error = Error::timeout_after(Duration::from_millis(250), Duration::from_millis(200));
+        assert_eq!(error.kind(), &ErrorKind::Timeout);
+        assert!(error.is_retryable());
+        assert_eq!(
+            error.context(),
+            vec![
+                (Cow::Borrowed("elapsed_ms"), Cow::Borrowed("250")),
+                (Cow::Borrowed("limit_ms"), Cow::Borrowed("200")),
+                (Cow::Borrowed("retry_after_ms"), Cow::Borrowed("220")),
+            ]
+        );
+
+        let tiny = Error::timeout_after(Duration::from_micros(900), Duration::from_micros(500));
+        assert_eq!(tiny.context()[2].1, "1");
+    }
+
+    #[test]
+    fn test_retry_after() {
+        use std::time::Duration;
+
+        assert_eq!(Error::timeout().retry_after(), None);
+
+        let error = Error::timeout()
+            .with_retry_after(Duration::from_millis(1500))
+            .with_context("user_id", "7")
+            .with_retry_after(Duration::from_secs(3));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(3)));
+        assert_eq!(error.context().len(), 2);
+
+        let error = Error::timeout().with_context(RETRY_AFTER_KEY, "soon");
+        assert_eq!(error.retry_after(), None);
+    }
+
+    #[test]
+    fn test_with_context_int_and_uint() {
+        for value in [
+            0,
+            7,
+            10,
+            42,
+            -1,
+            -10,
+            1234567890,
+            4_294_967_299,
+            i64::MAX,
+            i64::MIN,
+        ] {
+            let error = Error::not_found().with_context_int("n", value);
+            assert_eq!(error.context()[0].1, value.to_string(), "{value}");
+        }
+        for value in [0, 9, 10, 100, 4_294_967_299, u64::MAX] {
+            let error = Error::not_found().with_context_uint("n", value);
+            assert_eq!(error.context()[0].1, value.to_string(), "{value}");
+        }
+        assert!(matches!(
+            Error::not_found().with_context_uint("n", 3).context()[0].1,
+            Cow::Borrowed("3")
+        ));
+
+        let built = Error::builder(ErrorKind::NotFound, ErrorStatus::Permanent, "missing")
+            .with_context_int("offset", -5)
+            .with_context_uint("limit", 50)
+            .build();
+        assert_eq!(built.to_string(), "missing [offset: -5, limit: 50]");
+    }
+
+    #[test]
+    fn test_origin_operation() {
+        let error = Error::not_found();
+        assert_eq!(error.origin_operation(), None);
+
+        let error = error.with_operation("load_row");
+        assert_eq!(error.origin_operation(), Some("load_row"));
+        assert!(!error.to_json().contains(r#""origin""#));
+
+        let error = error
+            .with_operation("get_user")
+            .with_operation("handle_request");
+        assert_eq!(error.operation(), Some("handle_request"));
+        assert_eq!(error.origin_operation(), Some("load_row"));
+
+        let json: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(json["operation"], "handle_request");
+        assert_eq!(json["origin"], "load_row");
+
+        let inherited = Error::unexpected().inherit_operation(&error);
+        assert_eq!(inherited.origin_operation(), Some("load_row"));
+    }
+
+    #[test]
+    fn test_into_parts_round_trip() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow")
+            .with_operation("fetch")
+            .with_context("a", "1")
+            .with_context("b", "2");
+        let context = error.context();
+
+        let (kind, status, message, operation, parts_context) = error.clone().into_parts();
+        assert_eq!(parts_context.to_vec(), context);
+        let rebuilt = Error::from_parts(kind, status, message, operation, parts_context);
+        assert_eq!(rebuilt.kind(), error.kind());
+        assert_eq!(rebuilt.status(), error.status());
+        assert_eq!(rebuilt.message(), error.message());
+        assert_eq!(rebuilt.operation(), error.operation());
+        assert_eq!(rebuilt.context(), context);
+        assert_eq!(rebuilt.to_json(), error.to_json());
+
+        let plain = Error::not_found().with_operation("lookup");
+        let (kind, status, message, operation, context) = plain.clone().into_parts();
+        assert_eq!(
+            Error::from_parts(kind, status, message, operation, context),
+            plain
+        );
+    }
+
+    #[test]
+    fn test_duration_and_timestamp_context() {
+        use std::time::{Duration, UNIX_EPOCH};
+
+        let error = Error::timeout()
+            .with_duration("elapsed", Duration::from_millis(5200))
+            .with_duration("sub_ms", Duration::from_micros(900))
+            .with_timestamp_context("at", UNIX_EPOCH + Duration::from_millis(1_700_000_000_123))
+            .with_timestamp_context("before", UNIX_EPOCH - Duration::from_secs(1));
+        let context = error.context();
+        assert_eq!(context[0], ("elapsed".into(), "5200".into()));
+        assert_eq!(context[1], ("sub_ms".into(), "0".into()));
+        assert_eq!(context[2], ("at".into(), "1700000000123".into()));
+        assert_eq!(context[3], ("before".into(), "-1000".into()));
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[test]
+    fn test_expect_kind() {
+        let error = Error::timeout();
+        error.expect_kind(ErrorKind::Timeout);
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            error.expect_kind(ErrorKind::NotFound);
+        }))
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("expected error kind `not_found`"),
+            "{message}"
+        );
+        assert!(message.contains("got `timeout`"), "{message}");
+    }
+
+    #[test]
+    fn test_display_with_options() {
+        let error = Error::not_found()
+            .with_operation("lookup")
+            .with_context("a", "1")
+            .with_context("b", "2");
+
+        assert_eq!(
+            error.display_with(DisplayOptions::default()).to_string(),
+            error.to_string()
+        );
+        assert_eq!(
+            error
+                .display_with(DisplayOptions::new().with_separator(" | "))
+                .to_string(),
+            "not found (operation: lookup) [a: 1 | b: 2]"
+        );
+        assert_eq!(
+            error
+                .display_with(DisplayOptions::new().with_context(false))
+                .to_string(),
+            "not found (operation: lookup)"
+        );
+        assert_eq!(
+            error
+                .display_with(
+                    DisplayOptions::new()
+                        .with_operation(false)
+                        .with_context(false)
+                )
+                .to_string(),
+            "not found"
+        );
+    }
+
+    #[test]
+    fn test_http_request_context_keys() {
+        let error = Error::unexpected()
+            .with_http_request("POST", "/orders")
+            .with_http_response(500)
+            .with_http_response(502);
+        assert_eq!(
+            error.context(),
+            vec![
+                (Cow::Borrowed("http.method"), Cow::Borrowed("POST")),
+                (Cow::Borrowed("http.path"), Cow::Borrowed("/orders")),
+                (Cow::Borrowed("http.status"), Cow::Borrowed("502")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_fields() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow upstream")
+            .with_operation("fetch")
+            .with_context("host", "db-1");
+        let fields = error.fields();
+        assert_eq!(fields.kind, &ErrorKind::Timeout);
+        assert_eq!(fields.status, &ErrorStatus::Temporary);
+        assert_eq!(fields.message, "slow upstream");
+        assert_eq!(fields.operation, Some("fetch"));
+        assert!(fields.retryable);
+        assert_eq!(
+            fields.context,
+            vec![(Cow::Borrowed("host"), Cow::Borrowed("db-1"))]
+        );
+        assert_eq!(ErrorFields::from(&error), fields);
+
+        let bare = Error::not_found();
+        let fields = bare.fields();
+        assert_eq!(fields.operation, None);
+        assert!(!fields.retryable);
+        assert!(fields.context.is_empty());
+    }
+
+    #[test]
+    fn test_mark_exhausted() {
+        let error = Error::timeout().mark_exhausted().mark_exhausted();
+        assert_eq!(error.status(), &ErrorStatus::Persistent);
+        assert!(!error.is_retryable());
+        assert_eq!(
+            error.context(),
+            vec![(Cow::Borrowed("retry_exhausted"), Cow::Borrowed("true"))]
+        );
+
+        let exn = error
+            .raise()
+            .raise(Error::permanent(ErrorKind::Unexpected, "sync failed"));
+        assert!(!crate::has_retryable(&exn));
+
+        assert!(Error::not_found().mark_exhausted().is_permanent());
+    }
+
+    #[test]
+    fn test_heap_size() {
+        assert_eq!(Error::not_found().heap_size(), 0);
+        assert_eq!(
+            Error::permanent(ErrorKind::NotFound, "static message").heap_size(),
+            0
+        );
+
+        let owned = Error::permanent(ErrorKind::NotFound, String::from("owned"));
+        assert!(owned.heap_size() >= 5);
+
+        let mut heavy = Error::not_found();
+        for i in 0..20 {
+            heavy = heavy.with_context(format!("key_{i}"), "x".repeat(100));
+        }
+        assert!(heavy.heap_size() >= 20 * 100, "{}", heavy.heap_size());
+        assert!(heavy.clone_shallow().heap_size() >= 20 * 100);
+    }
+
+    #[test]
+    fn test_clone_shallow_shares_context() {
+        use crate::arena::ContextHandle;
+
+        let mut pairs = ContextVec::new();
+        pairs.push(("user_id".into(), "7".into()));
+        pairs.push(("region".into(), "eu".into()));
+        let error = Error::from_parts(
+            ErrorKind::NotFound,
+            ErrorStatus::Permanent,
+            "missing".into(),
+            Some("load"),
+            pairs,
+        )
+        .with_code("E404");
+
+        let shallow = error.clone_shallow();
+        assert!(matches!(shallow.context, ContextHandle::Shared(_)));
+        assert!(shallow.eq_unordered_context(&error));
+        assert_eq!(shallow.context(), error.context());
+
+        let again = shallow.clone_shallow();
+        match (&shallow.context, &again.context) {
+            (ContextHandle::Shared(a), ContextHandle::Shared(b)) => assert!(Arc::ptr_eq(a, b)),
+            _ => panic!("expected shared context"),
+        }
+
+        let bare = Error::timeout().clone_shallow();
+        assert!(matches!(bare.context, ContextHandle::Empty));
+    }
+
+    #[test]
+    fn test_with_context_once_keeps_first() {
+        let error = Error::not_found()
+            .with_context_once("request_id", "inner")
+            .with_context("region", "eu")
+            .with_context_once("request_id", "outer");
+        assert_eq!(
+            error.context(),
+            vec![
+                (Cow::Borrowed("request_id"), Cow::Borrowed("inner")),
+                (Cow::Borrowed("region"), Cow::Borrowed("eu")),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_set_context_upserts() {
+        let error = Error::not_found()
+            .set_context("user_id", "1")
+            .with_context("region", "eu")
+            .set_context("user_id", "2");
+        assert_eq!(
+            error.context(),
+            vec![
+                (Cow::Borrowed("user_id"), Cow::Borrowed("2")),
+                (Cow::Borrowed("region"), Cow::Borrowed("eu")),
+            ]
+        );
+
+        let error = Error::not_found()
+            .with_context("k", "a")
+            .with_context("other", "x")
+            .with_context("k", "b")
+            .set_context("k", "c");
+        assert_eq!(error.context().len(), 2);
+        assert_eq!(error.context()[0], ("k".into(), "c".into()));
+        assert!(
+            error
+                .to_json()
+                .contains(r#""context":{"k":"c","other":"x"}"#)
+        );
+    }
+
+    #[test]
+    fn test_with_static_context() {
+        static TAGS: &[(&str, &str)] = &[("component", "billing"), ("region", "eu")];
+
+        let error = Error::not_found()
+            .with_context("id", "1")
+            .with_static_context(TAGS);
+        let context = error.context();
+        assert_eq!(context.len(), 3);
+        assert_eq!(context[1], ("component".into(), "billing".into()));
+        for (k, v) in &context[1..] {
+            assert!(matches!(k, Cow::Borrowed(_)));
+            assert!(matches!(v, Cow::Borrowed(_)));
+        }
+
+        let error = Error::not_found().with_static_context(&[]);
+        assert!(error.context().is_empty());
+    }
+
+    #[test]
+    fn test_to_problem_json() {
+        let error = Error::permanent(ErrorKind::NotFound, "user 7 does not exist")
+            .with_context("user_id", "7")
+            .with_context("status", "shadowed");
+        let json: serde_json::Value =
+            serde_json::from_str(&error.to_problem_json(Some("/users/7"))).unwrap();
+        assert_eq!(json["type"], "urn:erract:not_found");
+        assert_eq!(json["title"], "not found");
+        assert_eq!(json["status"], 404);
+        assert_eq!(json["detail"], "user 7 does not exist");
+        assert_eq!(json["instance"], "/users/7");
+        assert_eq!(json["user_id"], "7");
+
+        let repeated = Error::not_found()
+            .with_context("attempt", "1")
+            .with_context("attempt", "2")
+            .to_problem_json(None);
+        assert_eq!(repeated.matches(r#""attempt""#).count(), 1);
+        assert!(repeated.ends_with(r#","attempt":"2"}"#));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&Error::timeout().to_problem_json(None)).unwrap();
+        assert!(json.get("instance").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_with_context_json() {
+        let detail = serde_json::json!({"plan": "pro", "limits": [1, 2]});
+        let error = Error::not_found()
+            .with_context_json("account", &detail)
+            .with_context("note", "[not json");
+
+        let flat: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(flat["context"]["account"], detail.to_string());
+
+        let nested: serde_json::Value = serde_json::from_str(&error.to_json_structured()).unwrap();
+        assert_eq!(nested["context"]["account"], detail);
+        assert_eq!(nested["context"]["account"]["limits"][1], 2);
+        assert_eq!(nested["context"]["note"], "[not json");
+    }
+
+    #[cfg(all(feature = "http", feature = "db", feature = "storage"))]
+    #[test]
+    fn test_domain_constructors_pick_status() {
+        use crate::db::DatabaseErrorKind;
+        use crate::http::HttpErrorKind;
+        use crate::storage::StorageErrorKind;
+
+        let deadlock = Error::database(DatabaseErrorKind::Deadlock, "deadlock");
+        assert_eq!(deadlock.status(), &ErrorStatus::Temporary);
+        assert!(deadlock.is_retryable());
+        let syntax = Error::database(DatabaseErrorKind::QuerySyntax, "bad sql");
+        assert_eq!(syntax.status(), &ErrorStatus::Permanent);
+
+        let unavailable = Error::http(HttpErrorKind::ServerError(503), "unavailable");
+        assert_eq!(unavailable.status(), &ErrorStatus::Temporary);
+        assert_eq!(
+            unavailable.kind(),
+            &ErrorKind::Http(HttpErrorKind::ServerError(503))
+        );
+        let missing = Error::http(HttpErrorKind::ClientError(404), "missing");
+        assert_eq!(missing.status(), &ErrorStatus::Permanent);
+
+        let flaky = Error::storage(StorageErrorKind::IoError, "io");
+        assert_eq!(flaky.status(), &ErrorStatus::Temporary);
+        let gone = Error::storage(StorageErrorKind::NotFound, "gone");
+        assert_eq!(gone.status(), &ErrorStatus::Permanent);
+    }
+
+    #[cfg(feature = "http")]
+    #[test]
+    fn test_from_http_headers() {
+        use crate::http::HttpErrorKind;
+
+        let error = Error::from_http(
+            503,
+            &[
+                ("content-type", "text/html"),
+                ("Retry-After", "30"),
+                ("x-request-id", "req-42"),
+            ],
+        );
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::Http(HttpErrorKind::ServerError(503))
+        );
+        assert!(error.is_retryable());
+        assert_eq!(
+            error.context(),
+            vec![
+                (Cow::Borrowed("retry_after_ms"), Cow::Borrowed("30000")),
+                (Cow::Borrowed("request_id"), Cow::Borrowed("req-42")),
+            ]
+        );
+
+        let malformed = Error::from_http(
+            429,
+            &[
+                ("Retry-After", "Wed, 21 Oct 2015 07:28:00 GMT"),
+                ("X-Trace-Id", "  "),
+            ],
+        );
+        assert_eq!(
+            malformed.kind(),
+            &ErrorKind::Http(HttpErrorKind::RateLimited)
+        );
+        assert!(malformed.context().is_empty());
+    }
+
+    #[test]
+    fn test_to_json_flat() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow")
+            .with_operation("fetch")
+            .with_context("user_id", "123")
+            .with_context("kind", "batch")
+            .with_context("quote\"key", "v");
+        let json = error.to_json_flat();
+        assert_eq!(
+            json,
+            r#"{"kind":"timeout","status":"temporary","message":"slow","operation":"fetch","user_id":"123","context.kind":"batch","quote\"key":"v"}"#
+        );
+        assert!(!json.contains(r#""context":"#));
+        assert_eq!(
+            Error::not_found().to_json_flat(),
+            Error::not_found().to_json()
+        );
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_to_json_flat_parses() {
+        let error = Error::not_found()
+            .with_context("status", "x")
+            .with_context("region", "eu");
+        let parsed: serde_json::Value = serde_json::from_str(&error.to_json_flat()).unwrap();
+        assert_eq!(parsed["status"], "permanent");
+        assert_eq!(parsed["context.status"], "x");
+        assert_eq!(parsed["region"], "eu");
+    }
+
+    #[test]
+    fn test_to_kv() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow upstream")
+            .with_operation("fetch")
+            .with_context("host", "db-1")
+            .with_context("attempt", "2");
+        assert_eq!(
+            error.to_kv(),
+            vec![
+                (Cow::Borrowed("error.kind"), Cow::Borrowed("timeout")),
+                (Cow::Borrowed("error.status"), Cow::Borrowed("temporary")),
+                (
+                    Cow::Borrowed("error.message"),
+                    Cow::Borrowed("slow upstream")
+                ),
+                (Cow::Borrowed("error.operation"), Cow::Borrowed("fetch")),
+                (Cow::Borrowed("host"), Cow::Borrowed("db-1")),
+                (Cow::Borrowed("attempt"), Cow::Borrowed("2")),
+            ]
+        );
+
+        let kv = Error::not_found().to_kv();
+        assert_eq!(kv.len(), 3);
+        assert!(!kv.iter().any(|(k, _)| k == "error.operation"));
+    }
+
+    #[test]
+    fn test_to_json_sorted() {
+        let error = Error::not_found()
+            .with_context("zone", "eu-1")
+            .with_context("user", "42")
+            .with_context("attempt_id", "a7");
+        assert!(
+            error
+                .to_json()
+                .ends_with(r#""context":{"zone":"eu-1","user":"42","attempt_id":"a7"}}"#)
+        );
+        let sorted = error.to_json_sorted();
+        assert!(sorted.ends_with(r#""context":{"attempt_id":"a7","user":"42","zone":"eu-1"}}"#));
+        assert_eq!(
+            sorted.len(),
+            error.to_json().len(),
+            "only the order differs"
+        );
+
+        let mut buf = String::from("x");
+        error.write_json_sorted(&mut buf);
+        assert_eq!(&buf[1..], sorted);
+    }
+
+    #[test]
+    fn test_to_json_bytes() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow \"upstream\"\n")
+            .with_operation("fetch")
+            .with_attempt(2)
+            .with_context("path", "/ü/tab\t");
+        let bytes = error.to_json_bytes();
+        assert_eq!(bytes, error.to_json().into_bytes());
+
+        let parsed: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(parsed["message"], "slow \"upstream\"\n");
+        assert_eq!(parsed["context"]["path"], "/ü/tab\t");
+
+        let mut framed = b"len:".to_vec();
+        error.write_json_bytes(&mut framed);
+        assert_eq!(&framed[4..], &bytes[..]);
+    }
+
+    #[test]
+    fn test_with_code() {
+        let error = Error::permanent(ErrorKind::Validation, "card declined")
+            .with_code("billing.card_declined");
+        assert_eq!(error.code(), Some("billing.card_declined"));
+
+        let json: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(json["code"], "billing.card_declined");
+        assert!(
+            error
+                .to_machine_string()
+                .contains(";code=billing.card_declined")
+        );
+        assert_eq!(error.sanitize_for_client().code(), error.code());
+
+        let plain = Error::not_found();
+        assert_eq!(plain.code(), None);
+        let json: serde_json::Value = serde_json::from_str(&plain.to_json()).unwrap();
+        assert!(json.get("code").is_none());
+        assert!(!plain.to_machine_string().contains("code="));
+    }
+
+    #[cfg(feature = "storage")]
+    #[test]
+    fn test_flatten_domain_kind() {
+        use crate::storage::StorageErrorKind;
+
+        let error = Error::storage(StorageErrorKind::NotFound, "missing file")
+            .with_context("path", "/tmp/x")
+            .flatten_domain_kind();
+        assert_eq!(error.kind(), &ErrorKind::NotFound);
+        assert_eq!(error.message(), "missing file");
+        assert_eq!(error.context().len(), 1);
+
+        let error = Error::storage(StorageErrorKind::SymlinkLoop, "loop").flatten_domain_kind();
+        assert_eq!(
+            error.kind(),
+            &ErrorKind::Storage(StorageErrorKind::SymlinkLoop)
+        );
+
+        assert_eq!(
+            Error::timeout().flatten_domain_kind().kind(),
+            &ErrorKind::Timeout
+        );
+    }
+
+    #[test]
+    fn test_message_id() {
+        let error = Error::not_found()
+            .with_message_id("error.user_not_found")
+            .with_code("E404");
+        assert_eq!(error.message_id(), Some("error.user_not_found"));
+        assert_eq!(error.code(), Some("E404"));
+
+        let json: serde_json::Value = serde_json::from_str(&error.to_json()).unwrap();
+        assert_eq!(json["message_id"], "error.user_not_found");
+        assert_eq!(json["message"], "not found");
+        assert_eq!(
+            error.sanitize_for_client().message_id(),
+            Some("error.user_not_found")
+        );
+
+        assert_eq!(Error::not_found().message_id(), None);
+        let json: serde_json::Value = serde_json::from_str(&Error::not_found().to_json()).unwrap();
+        assert!(json.get("message_id").is_none());
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_labels_serde_round_trip() {
+        let error = Error::not_found()
+            .with_operation("load_row")
+            .with_operation("get_user")
+            .with_message_id("error.user_not_found")
+            .with_code("E404");
+        let json: &'static str = serde_json::to_string(&error).unwrap().leak();
+        let value: serde_json::Value = serde_json::from_str(json).unwrap();
+        assert_eq!(value["message_id"], "error.user_not_found");
+        assert_eq!(value["code"], "E404");
+
+        let back: Error = serde_json::from_str(json).unwrap();
+        assert_eq!(back, error);
+        assert_eq!(back.origin_operation(), Some("load_row"));
+
+        let plain = Error::permanent(ErrorKind::NotFound, "missing");
+        let json: &'static str = serde_json::to_string(&plain).unwrap().leak();
+        assert_eq!(serde_json::from_str::<Error>(json).unwrap(), plain);
+    }
+
     #[test]
     fn test_memory_size() {
         let size = std::mem::size_of::<Error>();
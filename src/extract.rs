@@ -1,19 +1,21 @@
-use crate::Error;
+use std::fmt::{self, Write};
+
+use crate::{Error, ErrorKind};
 use exn::Frame;
 use smallvec::SmallVec;
 
-/// An iterator that traverses the error frame tree in depth-first order.
+/// An iterator that traverses the error frame tree in depth-first pre-order.
 ///
 /// This iterator uses an inline stack (SmallVec) to avoid heap allocation
 /// for error trees with moderate depth (up to 16 levels).
-struct FrameIter<'a> {
+pub(crate) struct FrameIter<'a> {
     // 16 frames is enough for most practical error chains.
     // If deeper, it spills to the heap automatically.
     stack: SmallVec<[&'a Frame; 16]>,
 }
 
 impl<'a> FrameIter<'a> {
-    fn new(root: &'a Frame) -> Self {
+    pub(crate) fn new(root: &'a Frame) -> Self {
         let mut stack = SmallVec::new();
         stack.push(root);
         Self { stack }
@@ -26,11 +28,9 @@ impl<'a> Iterator for FrameIter<'a> {
     fn next(&mut self) -> Option<Self::Item> {
         let frame = self.stack.pop()?;
 
-        // Add children to the stack.
-        // Note: This results in traversing children in reverse order (last child first).
-        // Since we are only checking boolean properties or counting, strict order
-        // usually doesn't matter for these operations.
-        for child in frame.children() {
+        // Push children in reverse so they are visited first-to-last,
+        // giving a pre-order traversal (parent before its children).
+        for child in frame.children().iter().rev() {
             self.stack.push(child);
         }
 
@@ -45,9 +45,18 @@ pub fn count_frames(exn: &exn::Exn<Error>) -> usize {
     FrameIter::new(exn.as_frame()).count()
 }
 
-/// Gets the total number of errors in the tree (same as frame count).
+/// Gets the total number of errors in the tree.
+///
+/// Unlike [`count_frames`], this skips [context frames](Error::is_context_frame).
 pub fn count_errors(exn: &exn::Exn<Error>) -> usize {
-    count_frames(exn)
+    FrameIter::new(exn.as_frame())
+        .filter(|frame| {
+            frame
+                .as_any()
+                .downcast_ref::<Error>()
+                .is_none_or(|e| !e.is_context_frame())
+        })
+        .count()
 }
 
 /// Finds the first retryable error in the tree.
@@ -76,6 +85,19 @@ pub fn has_permanent(exn: &exn::Exn<Error>) -> bool {
     })
 }
 
+/// Returns `true` if any error in the tree is marked fatal.
+///
+/// Supervisors can use this to decide between crashing and recovering.
+/// This operation is iterative and safe for deep error trees.
+pub fn has_fatal(exn: &exn::Exn<Error>) -> bool {
+    FrameIter::new(exn.as_frame()).any(|frame| {
+        frame
+            .as_any()
+            .downcast_ref::<Error>()
+            .is_some_and(|e| e.is_fatal())
+    })
+}
+
 /// Checks if the error tree contains only retryable errors.
 ///
 /// This operation is iterative and safe for deep error trees.
@@ -88,12 +110,477 @@ pub fn is_all_retryable(exn: &exn::Exn<Error>) -> bool {
     })
 }
 
+/// Returns every [`Error`] in the tree, outermost first.
+///
+/// Frames are visited depth-first, with each frame preceding its children.
+/// Frames that do not hold an [`Error`] (e.g. wrapped source errors) are skipped.
+pub fn causes_outer_first(exn: &exn::Exn<Error>) -> Vec<&Error> {
+    FrameIter::new(exn.as_frame())
+        .filter_map(|frame| frame.as_any().downcast_ref::<Error>())
+        .collect()
+}
+
+/// Returns every [`Error`] in the tree, from the root cause to the outermost error.
+///
+/// This is the reverse of [`causes_outer_first`].
+pub fn causes(exn: &exn::Exn<Error>) -> Vec<&Error> {
+    let mut causes = causes_outer_first(exn);
+    causes.reverse();
+    causes
+}
+
+/// Builds one error tree from a batch of errors, e.g. failures of parallel tasks.
+///
+/// The first error becomes the root and the remaining errors its children, so
+/// every error appears exactly once. Returns `None` if `errors` is empty.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, extract::from_errors};
+///
+/// let results: Vec<Result<u32, Error>> = vec![Ok(1), Err(Error::timeout()), Err(Error::not_found())];
+/// let exn = from_errors(results.into_iter().filter_map(Result::err)).unwrap();
+/// assert_eq!(erract::count_errors(&exn), 2);
+/// ```
+#[track_caller]
+pub fn from_errors(errors: impl IntoIterator<Item = Error>) -> Option<exn::Exn<Error>> {
+    let mut errors = errors.into_iter();
+    let root = errors.next()?;
+    Some(exn::Exn::from_iter(errors, root))
+}
+
+/// Like [`from_errors`], but labels each error with the name of the task that
+/// produced it, as its `task` context value.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, extract::join_labeled};
+///
+/// let exn = join_labeled([("fetch_user", Error::timeout()), ("fetch_orders", Error::not_found())]).unwrap();
+/// assert_eq!(erract::count_errors(&exn), 2);
+/// assert_eq!(exn.as_error().context()[0].1, "fetch_user");
+/// ```
+#[track_caller]
+pub fn join_labeled(
+    results: impl IntoIterator<Item = (&'static str, Error)>,
+) -> Option<exn::Exn<Error>> {
+    let mut errors = results
+        .into_iter()
+        .map(|(label, error)| error.set_context("task", label));
+    let root = errors.next()?;
+    Some(exn::Exn::from_iter(errors, root))
+}
+
+/// Returns the most important error in the tree according to [`Error::severity_cmp`].
+///
+/// On a tie the outermost error wins.
+pub fn most_severe(exn: &exn::Exn<Error>) -> Option<&Error> {
+    FrameIter::new(exn.as_frame())
+        .filter_map(|frame| frame.as_any().downcast_ref::<Error>())
+        .reduce(|best, e| {
+            if e.severity_cmp(best).is_gt() {
+                e
+            } else {
+                best
+            }
+        })
+}
+
+/// Renders the whole tree as one line, joining frame messages with `": "`, outermost first.
+///
+/// Each frame is formatted with its `Display` impl, so context appears as `[key: value]`.
+pub fn display_chain(exn: &exn::Exn<Error>) -> String {
+    let mut out = String::new();
+    let _ = write_chain(&mut out, exn.as_frame());
+    out
+}
+
+pub(crate) fn write_chain(w: &mut impl fmt::Write, root: &Frame) -> fmt::Result {
+    // Context frames have no message, so their pairs lead into the wrapped error.
+    let mut separator = "";
+    for frame in FrameIter::new(root) {
+        w.write_str(separator)?;
+        write!(w, "{}", frame.as_error())?;
+        let context_frame = frame
+            .as_any()
+            .downcast_ref::<Error>()
+            .is_some_and(Error::is_context_frame);
+        separator = if context_frame { " " } else { ": " };
+    }
+    Ok(())
+}
+
+/// Renders a one-line headline for the tree, suitable as an alert title.
+///
+/// Combines the root cause's kind, the outermost operation, whether the
+/// outermost error is retryable, the frame count and the root cause's message.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, ErrorKind, extract::summarize};
+///
+/// let exn = Error::temporary(ErrorKind::Timeout, "connection timed out")
+///     .raise()
+///     .raise(Error::temporary(ErrorKind::Unexpected, "lookup failed").with_operation("user_service"));
+/// assert_eq!(
+///     summarize(&exn),
+///     "Timeout in user_service (retryable, 2 frames, root: connection timed out)"
+/// );
+/// ```
+pub fn summarize(exn: &exn::Exn<Error>) -> String {
+    let outer = exn.as_error();
+    let root = root_cause(exn).unwrap_or(outer);
+    let kind = root.kind().to_string();
+    let mut chars = kind.chars();
+    let mut out = String::with_capacity(64 + root.message().len());
+    if let Some(first) = chars.next() {
+        out.extend(first.to_uppercase());
+        out.push_str(chars.as_str());
+    }
+    if let Some(operation) = outer.operation() {
+        let _ = write!(out, " in {operation}");
+    }
+    let frames = count_frames(exn);
+    let _ = write!(
+        out,
+        " ({}, {frames} frame{}, root: {})",
+        if outer.is_retryable() {
+            "retryable"
+        } else {
+            "not retryable"
+        },
+        if frames == 1 { "" } else { "s" },
+        root.message()
+    );
+    out
+}
+
+/// Returns each distinct [`ErrorKind`] in the tree, in first-seen order (outermost first).
+pub fn distinct_kinds(exn: &exn::Exn<Error>) -> Vec<ErrorKind> {
+    let mut seen = std::collections::HashSet::new();
+    FrameIter::new(exn.as_frame())
+        .filter_map(|frame| frame.as_any().downcast_ref::<Error>())
+        .filter(|error| seen.insert(error.kind()))
+        .map(|error| error.kind().clone())
+        .collect()
+}
+
+/// Joins the [`distinct_kinds`] of the tree with `" + "`, as a compact label for
+/// a fan-in error.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, extract::{cause_summary, from_errors}};
+///
+/// let exn = from_errors([Error::not_found(), Error::timeout(), Error::not_found()]).unwrap();
+/// assert_eq!(cause_summary(&exn), "not found + timeout");
+/// ```
+pub fn cause_summary(exn: &exn::Exn<Error>) -> String {
+    let mut out = String::new();
+    for kind in distinct_kinds(exn) {
+        if !out.is_empty() {
+            out.push_str(" + ");
+        }
+        let _ = write!(out, "{kind}");
+    }
+    out
+}
+
+/// Serializes the tree as a flat JSON array of frames, outermost first.
+///
+/// Each element is the frame's [`Error::to_json`] object with an extra `"depth"`
+/// member (0 for the outermost frame), which is easier to index than nested trees.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, extract::to_json_array};
+///
+/// let exn = Error::timeout().raise();
+/// assert!(to_json_array(&exn).ends_with(r#","depth":0}]"#));
+/// ```
+pub fn to_json_array(exn: &exn::Exn<Error>) -> String {
+    let mut out = String::with_capacity(256);
+    out.push('[');
+    let mut stack: SmallVec<[(&Frame, usize); 16]> = SmallVec::new();
+    stack.push((exn.as_frame(), 0));
+    while let Some((frame, depth)) = stack.pop() {
+        for child in frame.children().iter().rev() {
+            stack.push((child, depth + 1));
+        }
+        let Some(error) = frame.as_any().downcast_ref::<Error>() else {
+            continue;
+        };
+        if out.len() > 1 {
+            out.push(',');
+        }
+        error.write_json(&mut out);
+        // Reopen the object to append the depth member.
+        out.pop();
+        let _ = write!(out, r#","depth":{depth}}}"#);
+    }
+    out.push(']');
+    out
+}
+
+/// Returns the deepest [`Error`] in the tree, the one furthest from the outermost frame.
+///
+/// When several errors share the greatest depth, the first in depth-first order wins.
+pub fn root_cause(exn: &exn::Exn<Error>) -> Option<&Error> {
+    deepest_matching(exn, |_| true)
+}
+
+/// Returns the deepest retryable [`Error`] in the tree, the retryable cause
+/// closest to the root.
+///
+/// Useful for reading the [retry hint](Error::retry_after) that matters most.
+/// When several retryable errors share the greatest depth, the first in
+/// depth-first order wins.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use erract::{Error, ErrorKind, extract::deepest_retryable};
+///
+/// let exn = Error::timeout_after(Duration::from_secs(3), Duration::from_secs(2))
+///     .raise()
+///     .raise(Error::permanent(ErrorKind::Unexpected, "sync failed"));
+/// let cause = deepest_retryable(&exn).unwrap();
+/// assert_eq!(cause.kind(), &ErrorKind::Timeout);
+/// assert_eq!(cause.retry_after(), Some(Duration::from_millis(2200)));
+/// ```
+pub fn deepest_retryable(exn: &exn::Exn<Error>) -> Option<&Error> {
+    deepest_matching(exn, Error::is_retryable)
+}
+
+fn deepest_matching(exn: &exn::Exn<Error>, pred: impl Fn(&Error) -> bool) -> Option<&Error> {
+    let mut deepest: Option<(&Error, usize)> = None;
+    let mut stack: SmallVec<[(&Frame, usize); 16]> = SmallVec::new();
+    stack.push((exn.as_frame(), 0));
+    while let Some((frame, depth)) = stack.pop() {
+        for child in frame.children().iter().rev() {
+            stack.push((child, depth + 1));
+        }
+        if let Some(error) = frame.as_any().downcast_ref::<Error>()
+            && deepest.is_none_or(|(_, d)| depth > d)
+            && pred(error)
+        {
+            deepest = Some((error, depth));
+        }
+    }
+    deepest.map(|(error, _)| error)
+}
+
+/// Returns `true` if both trees have the same [`root_cause`], for grouping
+/// independent failures into one incident.
+///
+/// Root causes match on kind, message, operation and code; context, status and
+/// the wrapping frames are ignored.
+pub fn same_root_cause(a: &exn::Exn<Error>, b: &exn::Exn<Error>) -> bool {
+    match (root_cause(a), root_cause(b)) {
+        (Some(a), Some(b)) => {
+            a.kind() == b.kind()
+                && a.message() == b.message()
+                && a.operation() == b.operation()
+                && a.code() == b.code()
+        }
+        _ => false,
+    }
+}
+
+/// Rebuilds the tree with `f` applied to every error, keeping its shape.
+///
+/// Frames cannot be moved out of an `Exn`, so each error is cloned before being
+/// passed to `f`, and the rebuilt frames record the caller's location. Frames
+/// holding other error types become [`ErrorKind::Unexpected`] errors with their
+/// `Display` output as the message. The tree is rebuilt iteratively, so deep
+/// trees are safe.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, extract::map_errors};
+///
+/// let exn = Error::not_found().raise().raise(Error::timeout());
+/// let sanitized = map_errors(exn, |e| e.sanitize_for_client());
+/// assert_eq!(sanitized.as_error().message(), "The operation timed out.");
+/// ```
+#[track_caller]
+pub fn map_errors(exn: exn::Exn<Error>, f: impl Fn(Error) -> Error) -> exn::Exn<Error> {
+    // Pre-order guarantees every child comes after its parent, so walking the
+    // list backwards builds children before the frames that hold them.
+    let mut nodes: Vec<(&Frame, Option<usize>)> = Vec::new();
+    let mut stack: SmallVec<[(&Frame, Option<usize>); 16]> = SmallVec::new();
+    stack.push((exn.as_frame(), None));
+    while let Some((frame, parent)) = stack.pop() {
+        let index = nodes.len();
+        nodes.push((frame, parent));
+        for child in frame.children().iter().rev() {
+            stack.push((child, Some(index)));
+        }
+    }
+
+    let mut children: Vec<Vec<exn::Exn<Error>>> = (0..nodes.len()).map(|_| Vec::new()).collect();
+    let mut root = None;
+    for (index, (frame, parent)) in nodes.iter().enumerate().rev() {
+        let error = match frame.as_any().downcast_ref::<Error>() {
+            Some(error) => error.clone(),
+            None => Error::new_detached(
+                ErrorKind::Unexpected,
+                crate::ErrorStatus::Permanent,
+                frame.as_error().to_string(),
+            ),
+        };
+        let mut own = std::mem::take(&mut children[index]);
+        own.reverse();
+        let rebuilt = exn::Exn::from_iter(own, f(error));
+        match parent {
+            Some(parent) => children[*parent].push(rebuilt),
+            None => root = Some(rebuilt),
+        }
+    }
+    root.expect("the root frame is always rebuilt")
+}
+
+/// Assertions on error trees, meant for tests.
+#[cfg(feature = "test-helpers")]
+pub trait ExpectKindExt {
+    /// Panics unless the [`root_cause`] of the tree has the given kind.
+    ///
+    /// The panic message names both kinds and includes the whole chain.
+    #[track_caller]
+    fn expect_root_cause_kind(&self, kind: ErrorKind);
+}
+
+#[cfg(feature = "test-helpers")]
+impl ExpectKindExt for exn::Exn<Error> {
+    fn expect_root_cause_kind(&self, kind: ErrorKind) {
+        let actual = root_cause(self).map(Error::kind);
+        if actual != Some(&kind) {
+            panic!(
+                "expected root cause kind `{}`, got `{}`: {}",
+                kind.to_machine_string(),
+                actual.map_or("none".into(), ErrorKind::to_machine_string),
+                display_chain(self)
+            );
+        }
+    }
+}
+
+fn find_error(exn: &exn::Exn<Error>, predicate: impl Fn(&Error) -> bool) -> Option<&Error> {
+    FrameIter::new(exn.as_frame())
+        .filter_map(|frame| frame.as_any().downcast_ref::<Error>())
+        .find(|e| predicate(e))
+}
+
+/// Extension trait for observing errors in a `Result` without consuming it.
+pub trait InspectErrorExt: Sized {
+    /// Calls `f` with the first error in the tree of the given kind, outermost first.
+    fn inspect_err_kind(self, kind: ErrorKind, f: impl FnOnce(&Error)) -> Self;
+
+    /// Calls `f` with the first retryable error in the tree, outermost first.
+    fn inspect_retryable(self, f: impl FnOnce(&Error)) -> Self;
+}
+
+impl<T> InspectErrorExt for crate::Result<T> {
+    #[inline]
+    fn inspect_err_kind(self, kind: ErrorKind, f: impl FnOnce(&Error)) -> Self {
+        if let Err(exn) = &self
+            && let Some(error) = find_error(exn, |e| e.kind() == &kind)
+        {
+            f(error);
+        }
+        self
+    }
+
+    #[inline]
+    fn inspect_retryable(self, f: impl FnOnce(&Error)) -> Self {
+        if let Err(exn) = &self
+            && let Some(error) = find_error(exn, Error::is_retryable)
+        {
+            f(error);
+        }
+        self
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::ErrorKind;
     use exn::{ResultExt, bail};
 
+    #[test]
+    fn test_deepest_retryable() {
+        let exn = Error::temporary(ErrorKind::Timeout, "root")
+            .raise()
+            .raise(Error::temporary(ErrorKind::Unexpected, "middle"))
+            .raise(Error::permanent(ErrorKind::Unexpected, "outer"));
+        assert_eq!(deepest_retryable(&exn).unwrap().message(), "root");
+
+        let exn = Error::not_found()
+            .raise()
+            .raise(Error::temporary(ErrorKind::Unexpected, "middle"))
+            .raise(Error::permanent(ErrorKind::Unexpected, "outer"));
+        assert_eq!(deepest_retryable(&exn).unwrap().message(), "middle");
+
+        let exn = Error::not_found().raise();
+        assert!(deepest_retryable(&exn).is_none());
+    }
+
+    #[test]
+    fn test_distinct_kinds() {
+        let exn = from_errors([
+            Error::not_found(),
+            Error::timeout(),
+            Error::not_found(),
+            Error::validation_failed(),
+            Error::timeout(),
+        ])
+        .unwrap();
+        assert_eq!(
+            distinct_kinds(&exn),
+            [
+                ErrorKind::NotFound,
+                ErrorKind::Timeout,
+                ErrorKind::Validation
+            ]
+        );
+        assert_eq!(
+            cause_summary(&exn),
+            "not found + timeout + validation error"
+        );
+
+        let exn = Error::timeout().raise().raise(Error::timeout());
+        assert_eq!(cause_summary(&exn), "timeout");
+    }
+
+    #[test]
+    fn test_summarize() {
+        let exn = Error::temporary(ErrorKind::Timeout, "connection timed out")
+            .raise()
+            .raise(Error::temporary(ErrorKind::Unexpected, "query failed"))
+            .raise(
+                Error::temporary(ErrorKind::Unexpected, "lookup failed")
+                    .with_operation("user_service"),
+            );
+        assert_eq!(
+            summarize(&exn),
+            "Timeout in user_service (retryable, 3 frames, root: connection timed out)"
+        );
+
+        let single = Error::permanent(ErrorKind::NotFound, "no such user").raise();
+        assert_eq!(
+            summarize(&single),
+            "Not found (not retryable, 1 frame, root: no such user)"
+        );
+    }
+
     #[test]
     fn test_count_frames() {
         fn inner() -> crate::Result<()> {
@@ -145,6 +632,21 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_has_fatal() {
+        fn inner(fatal: bool) -> crate::Result<()> {
+            let error = Error::permanent(ErrorKind::Unexpected, "corrupted state");
+            bail!(if fatal { error.mark_fatal() } else { error });
+        }
+
+        fn outer(fatal: bool) -> crate::Result<()> {
+            inner(fatal).or_raise(|| Error::permanent(ErrorKind::Unexpected, "outer"))
+        }
+
+        assert!(has_fatal(&outer(true).unwrap_err()));
+        assert!(!has_fatal(&outer(false).unwrap_err()));
+    }
+
     #[test]
     fn test_has_retryable_only_permanent() {
         fn inner() -> crate::Result<()> {
@@ -196,6 +698,128 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_from_errors() {
+        let errors = vec![
+            Error::timeout(),
+            Error::not_found(),
+            Error::permanent(ErrorKind::Validation, "bad input"),
+        ];
+        let exn = from_errors(errors).unwrap();
+        assert_eq!(count_errors(&exn), 3);
+        assert_eq!(exn.as_error().kind(), &ErrorKind::Timeout);
+        assert_eq!(exn.as_frame().children().len(), 2);
+
+        assert!(from_errors(Vec::new()).is_none());
+    }
+
+    #[test]
+    fn test_most_severe() {
+        let exn = Error::temporary(ErrorKind::Timeout, "slow")
+            .raise()
+            .raise(Error::permanent(ErrorKind::Unexpected, "broken"))
+            .raise(Error::permanent(ErrorKind::NotFound, "outer"));
+        assert_eq!(most_severe(&exn).unwrap().message(), "broken");
+
+        let exn = Error::permanent(ErrorKind::NotFound, "inner")
+            .raise()
+            .raise(Error::permanent(ErrorKind::NotFound, "outer"));
+        assert_eq!(most_severe(&exn).unwrap().message(), "outer");
+    }
+
+    #[cfg(feature = "db")]
+    #[test]
+    fn test_most_severe_critical() {
+        use crate::db::DatabaseErrorKind;
+
+        let disk_full = ErrorKind::Database(DatabaseErrorKind::DiskFull);
+        let exn = Error::permanent(disk_full.clone(), "disk full")
+            .raise()
+            .raise(Error::permanent(ErrorKind::Unexpected, "write failed"))
+            .raise(Error::temporary(ErrorKind::Timeout, "request failed"));
+        assert_eq!(most_severe(&exn).unwrap().kind(), &disk_full);
+    }
+
+    #[test]
+    fn test_display_chain() {
+        fn inner() -> crate::Result<()> {
+            bail!(Error::not_found().with_context("id", "7"));
+        }
+
+        fn middle() -> crate::Result<()> {
+            inner().or_raise(|| Error::permanent(ErrorKind::Unexpected, "load failed"))
+        }
+
+        fn outer() -> crate::Result<()> {
+            middle().or_raise(|| Error::permanent(ErrorKind::Unexpected, "request failed"))
+        }
+
+        let exn = outer().unwrap_err();
+        assert_eq!(
+            display_chain(&exn),
+            "request failed: load failed: not found [id: 7]"
+        );
+    }
+
+    #[test]
+    fn test_causes_order() {
+        fn inner() -> crate::Result<()> {
+            bail!(Error::permanent(ErrorKind::NotFound, "inner"));
+        }
+
+        fn middle() -> crate::Result<()> {
+            inner().or_raise(|| Error::temporary(ErrorKind::Unexpected, "middle"))?;
+            Ok(())
+        }
+
+        fn outer() -> crate::Result<()> {
+            middle().or_raise(|| Error::temporary(ErrorKind::Unexpected, "outer"))?;
+            Ok(())
+        }
+
+        let exn = outer().unwrap_err();
+        let messages: Vec<_> = causes(&exn).iter().map(|e| e.message()).collect();
+        assert_eq!(messages, ["inner", "middle", "outer"]);
+
+        let messages: Vec<_> = causes_outer_first(&exn)
+            .iter()
+            .map(|e| e.message())
+            .collect();
+        assert_eq!(messages, ["outer", "middle", "inner"]);
+    }
+
+    #[test]
+    fn test_inspect_err_kind() {
+        let mut seen = Vec::new();
+        let result: crate::Result<()> = Err(Error::timeout().raise());
+        let result = result
+            .inspect_err_kind(ErrorKind::Timeout, |e| seen.push(e.message().to_owned()))
+            .inspect_err_kind(ErrorKind::NotFound, |_| panic!("kind does not match"));
+        assert!(result.is_err());
+        assert_eq!(seen, ["operation timed out"]);
+
+        let ok: crate::Result<u32> = Ok(1);
+        let ok = ok.inspect_err_kind(ErrorKind::Timeout, |_| panic!("result is ok"));
+        assert_eq!(ok.unwrap(), 1);
+    }
+
+    #[test]
+    fn test_inspect_retryable() {
+        let mut calls = 0;
+        let result: crate::Result<()> = Err(Error::timeout().raise());
+        let result = result
+            .or_raise(|| Error::permanent(ErrorKind::Unexpected, "outer"))
+            .inspect_retryable(|e| {
+                calls += 1;
+                assert_eq!(e.kind(), &ErrorKind::Timeout);
+            });
+        assert!(result.is_err());
+        assert_eq!(calls, 1);
+
+        let result: crate::Result<()> = Err(Error::not_found().raise());
+        let _ = result.inspect_retryable(|_| panic!("nothing is retryable"));
+    }
+
     #[test]
     fn test_deep_recursion_safety() {
         // Create a deep error tree (1000 levels) to verify no stack overflow
@@ -217,4 +841,146 @@ mod tests {
             panic!("expected error");
         }
     }
+
+    #[test]
+    fn test_to_json_array() {
+        let leaf = Error::not_found().with_context("id", "7").raise();
+        let middle = leaf.raise(Error::timeout());
+        let sibling = Error::unexpected().raise();
+        let exn = exn::Exn::from_iter([middle, sibling], Error::validation_failed());
+
+        let json = to_json_array(&exn);
+        let frames: Vec<serde_json::Value> = serde_json::from_str(&json).unwrap();
+        assert_eq!(frames.len(), count_frames(&exn));
+
+        let summary: Vec<(&str, u64)> = frames
+            .iter()
+            .map(|f| (f["kind"].as_str().unwrap(), f["depth"].as_u64().unwrap()))
+            .collect();
+        assert_eq!(
+            summary,
+            [
+                ("validation_error", 0),
+                ("timeout", 1),
+                ("not_found", 2),
+                ("unexpected_error", 1)
+            ]
+        );
+        assert_eq!(frames[2]["context"]["id"], "7");
+    }
+
+    #[test]
+    fn test_same_root_cause() {
+        let cause = || {
+            Error::temporary(ErrorKind::Timeout, "db read timed out").with_operation("load_user")
+        };
+
+        let a = cause()
+            .with_context("attempt", "1")
+            .raise()
+            .raise(Error::unexpected())
+            .raise(Error::permanent(ErrorKind::Validation, "handler failed"));
+        let b = cause()
+            .with_context("attempt", "3")
+            .raise()
+            .raise(Error::timeout());
+
+        assert_eq!(root_cause(&a).unwrap().message(), "db read timed out");
+        assert!(same_root_cause(&a, &b));
+        assert!(same_root_cause(&b, &a));
+
+        let other = Error::not_found().raise().raise(Error::unexpected());
+        assert!(!same_root_cause(&a, &other));
+    }
+
+    #[test]
+    fn test_map_errors() {
+        let left = Error::not_found()
+            .with_context("id", "7")
+            .raise()
+            .raise(Error::timeout());
+        let right = Error::unexpected().raise();
+        let exn = exn::Exn::from_iter([left, right], Error::validation_failed());
+        let before: Vec<(ErrorKind, String)> = causes_outer_first(&exn)
+            .into_iter()
+            .map(|e| (e.kind().clone(), e.message().to_uppercase()))
+            .collect();
+        let frames = count_frames(&exn);
+
+        let mapped = map_errors(exn, |e| {
+            let message = e.message().to_uppercase();
+            Error::new(e.kind().clone(), e.status().clone(), message).merge_context(&e)
+        });
+
+        assert_eq!(count_frames(&mapped), frames);
+        assert_eq!(mapped.as_frame().children().len(), 2);
+        assert_eq!(mapped.as_frame().children()[0].children().len(), 1);
+        let after: Vec<(ErrorKind, String)> = causes_outer_first(&mapped)
+            .into_iter()
+            .map(|e| (e.kind().clone(), e.message().to_owned()))
+            .collect();
+        assert_eq!(after, before);
+        assert_eq!(root_cause(&mapped).unwrap().context().len(), 1);
+    }
+
+    #[cfg(feature = "ambient")]
+    #[test]
+    fn test_map_errors_placeholder_skips_ambient_context() {
+        let exn = exn::Exn::new(std::io::Error::other("disk full")).raise(Error::timeout());
+        let _request = crate::context::scope(&[("request_id", "r-9")]);
+        let mapped = map_errors(exn, |e| e);
+        let placeholder = root_cause(&mapped).unwrap();
+        assert_eq!(placeholder.message(), "disk full");
+        assert!(placeholder.context().is_empty());
+    }
+
+    #[cfg(feature = "test-helpers")]
+    #[test]
+    fn test_expect_root_cause_kind() {
+        let exn = Error::not_found().raise().raise(Error::unexpected());
+        exn.expect_root_cause_kind(ErrorKind::NotFound);
+
+        let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            exn.expect_root_cause_kind(ErrorKind::Timeout);
+        }))
+        .unwrap_err();
+        let message = panic.downcast_ref::<String>().unwrap();
+        assert!(
+            message.contains("expected root cause kind `timeout`"),
+            "{message}"
+        );
+        assert!(message.contains("got `not_found`"), "{message}");
+    }
+
+    #[test]
+    fn test_join_labeled() {
+        assert!(join_labeled([]).is_none());
+
+        let exn = join_labeled([
+            ("fetch_user", Error::timeout()),
+            (
+                "fetch_orders",
+                Error::not_found().with_context("order", "9"),
+            ),
+            ("fetch_prefs", Error::unexpected()),
+        ])
+        .unwrap();
+        assert_eq!(count_errors(&exn), 3);
+
+        let labels: Vec<(ErrorKind, String)> = causes_outer_first(&exn)
+            .into_iter()
+            .map(|e| {
+                let task = e.context().into_iter().find(|(k, _)| k == "task").unwrap();
+                (e.kind().clone(), task.1.into_owned())
+            })
+            .collect();
+        assert_eq!(
+            labels,
+            [
+                (ErrorKind::Timeout, "fetch_user".to_owned()),
+                (ErrorKind::NotFound, "fetch_orders".to_owned()),
+                (ErrorKind::Unexpected, "fetch_prefs".to_owned()),
+            ]
+        );
+    }
 }
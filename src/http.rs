@@ -1,6 +1,9 @@
 use std::borrow::Cow;
 use std::fmt;
 
+use crate::kind::Severity;
+use crate::status::ErrorStatus;
+
 /// HTTP-specific error kinds.
 ///
 /// These errors categorize HTTP-related failures by what the caller should do.
@@ -12,7 +15,7 @@ pub enum HttpErrorKind {
     /// Don't retry - the request is malformed or unauthorized.
     ClientError(u16),
     /// Server error (5xx status codes).
-    /// May be temporary - safe to retry with backoff.
+    /// May be temporary - safe to retry with backoff, except 501 and 505.
     ServerError(u16),
     /// Rate limited by the server.
     /// Slow down and retry with backoff.
@@ -35,6 +38,12 @@ pub enum HttpErrorKind {
     /// Request timeout.
     /// Safe to retry with longer timeout.
     RequestTimeout,
+    /// Timed out establishing the connection.
+    /// Safe to retry - nothing was sent, so a short backoff is enough.
+    ConnectTimeout,
+    /// Timed out waiting for the response after the request was sent.
+    /// Safe to retry with a longer backoff - the server may still be working.
+    ReadTimeout,
     /// Content encoding error.
     /// May be temporary - safe to retry.
     EncodingError,
@@ -44,11 +53,33 @@ pub enum HttpErrorKind {
 }
 
 impl HttpErrorKind {
+    /// Returns every variant, using representative status codes for code-carrying variants.
+    #[inline]
+    pub fn all() -> &'static [HttpErrorKind] {
+        &[
+            HttpErrorKind::ClientError(400),
+            HttpErrorKind::ServerError(500),
+            HttpErrorKind::RateLimited,
+            HttpErrorKind::NetworkError,
+            HttpErrorKind::TlsError,
+            HttpErrorKind::InvalidUrl,
+            HttpErrorKind::RedirectLoop,
+            HttpErrorKind::TooManyRedirects,
+            HttpErrorKind::RequestTimeout,
+            HttpErrorKind::ConnectTimeout,
+            HttpErrorKind::ReadTimeout,
+            HttpErrorKind::EncodingError,
+            HttpErrorKind::DecodingError,
+        ]
+    }
+
     /// Returns `true` if this HTTP error kind represents a retryable condition.
     #[inline]
     pub fn is_retryable(&self) -> bool {
         match self {
             HttpErrorKind::ClientError(_) => false,
+            // 501 Not Implemented and 505 HTTP Version Not Supported won't change on retry.
+            HttpErrorKind::ServerError(501 | 505) => false,
             HttpErrorKind::ServerError(_) => true,
             HttpErrorKind::RateLimited => true,
             HttpErrorKind::NetworkError => true,
@@ -57,11 +88,20 @@ impl HttpErrorKind {
             HttpErrorKind::RedirectLoop => false,
             HttpErrorKind::TooManyRedirects => false,
             HttpErrorKind::RequestTimeout => true,
+            HttpErrorKind::ConnectTimeout => true,
+            HttpErrorKind::ReadTimeout => true,
             HttpErrorKind::EncodingError => true,
             HttpErrorKind::DecodingError => false,
         }
     }
 
+    /// Returns the status matching [`is_retryable`](HttpErrorKind::is_retryable):
+    /// `Temporary` for retryable kinds, `Permanent` otherwise.
+    #[inline]
+    pub fn default_status(&self) -> ErrorStatus {
+        ErrorStatus::from_retryable(self.is_retryable())
+    }
+
     /// Returns the HTTP status code if applicable.
     #[inline]
     pub fn status_code(&self) -> Option<u16> {
@@ -71,6 +111,47 @@ impl HttpErrorKind {
         }
     }
 
+    /// Returns the default severity of this error kind.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        match self {
+            HttpErrorKind::ClientError(_) => Severity::Warning,
+            HttpErrorKind::RateLimited => Severity::Warning,
+            HttpErrorKind::NetworkError => Severity::Warning,
+            HttpErrorKind::RequestTimeout => Severity::Warning,
+            HttpErrorKind::ConnectTimeout => Severity::Warning,
+            HttpErrorKind::ReadTimeout => Severity::Warning,
+            HttpErrorKind::ServerError(_) => Severity::Error,
+            HttpErrorKind::TlsError => Severity::Error,
+            HttpErrorKind::InvalidUrl => Severity::Error,
+            HttpErrorKind::RedirectLoop => Severity::Error,
+            HttpErrorKind::TooManyRedirects => Severity::Error,
+            HttpErrorKind::EncodingError => Severity::Error,
+            HttpErrorKind::DecodingError => Severity::Error,
+        }
+    }
+
+    /// Returns the HTTP status code to respond with for this error kind.
+    ///
+    /// Upstream transport failures map to `502 Bad Gateway`.
+    #[inline]
+    pub fn http_status(&self) -> u16 {
+        match self {
+            HttpErrorKind::ClientError(code) | HttpErrorKind::ServerError(code) => *code,
+            HttpErrorKind::RateLimited => 429,
+            HttpErrorKind::InvalidUrl => 400,
+            HttpErrorKind::RequestTimeout
+            | HttpErrorKind::ConnectTimeout
+            | HttpErrorKind::ReadTimeout => 504,
+            HttpErrorKind::NetworkError
+            | HttpErrorKind::TlsError
+            | HttpErrorKind::RedirectLoop
+            | HttpErrorKind::TooManyRedirects
+            | HttpErrorKind::EncodingError
+            | HttpErrorKind::DecodingError => 502,
+        }
+    }
+
     /// Creates an `HttpErrorKind` from an HTTP status code.
     ///
     /// # Examples
@@ -132,6 +213,8 @@ impl HttpErrorKind {
             HttpErrorKind::RedirectLoop => "Redirect Loop",
             HttpErrorKind::TooManyRedirects => "Too Many Redirects",
             HttpErrorKind::RequestTimeout => "Request Timeout",
+            HttpErrorKind::ConnectTimeout => "Connect Timeout",
+            HttpErrorKind::ReadTimeout => "Read Timeout",
             HttpErrorKind::EncodingError => "Encoding Error",
             HttpErrorKind::DecodingError => "Decoding Error",
         }
@@ -150,10 +233,46 @@ impl HttpErrorKind {
             HttpErrorKind::RedirectLoop => Cow::Borrowed("redirect_loop"),
             HttpErrorKind::TooManyRedirects => Cow::Borrowed("too_many_redirects"),
             HttpErrorKind::RequestTimeout => Cow::Borrowed("request_timeout"),
+            HttpErrorKind::ConnectTimeout => Cow::Borrowed("connect_timeout"),
+            HttpErrorKind::ReadTimeout => Cow::Borrowed("read_timeout"),
             HttpErrorKind::EncodingError => Cow::Borrowed("encoding_error"),
             HttpErrorKind::DecodingError => Cow::Borrowed("decoding_error"),
         }
     }
+
+    /// Writes the output of [`HttpErrorKind::to_machine_string`] without allocating.
+    pub fn write_machine_string(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            HttpErrorKind::ClientError(code) => write!(w, "client_error_{code}"),
+            HttpErrorKind::ServerError(code) => write!(w, "server_error_{code}"),
+            other => w.write_str(&other.to_machine_string()),
+        }
+    }
+
+    /// Parses the output of [`HttpErrorKind::to_machine_string`] back into a kind.
+    pub fn from_machine_string(s: &str) -> Option<Self> {
+        if let Some(code) = s.strip_prefix("client_error_") {
+            return code.parse().ok().map(HttpErrorKind::ClientError);
+        }
+        if let Some(code) = s.strip_prefix("server_error_") {
+            return code.parse().ok().map(HttpErrorKind::ServerError);
+        }
+        let kind = match s {
+            "rate_limited" => HttpErrorKind::RateLimited,
+            "network_error" => HttpErrorKind::NetworkError,
+            "tls_error" => HttpErrorKind::TlsError,
+            "invalid_url" => HttpErrorKind::InvalidUrl,
+            "redirect_loop" => HttpErrorKind::RedirectLoop,
+            "too_many_redirects" => HttpErrorKind::TooManyRedirects,
+            "request_timeout" => HttpErrorKind::RequestTimeout,
+            "connect_timeout" => HttpErrorKind::ConnectTimeout,
+            "read_timeout" => HttpErrorKind::ReadTimeout,
+            "encoding_error" => HttpErrorKind::EncodingError,
+            "decoding_error" => HttpErrorKind::DecodingError,
+            _ => return None,
+        };
+        Some(kind)
+    }
 }
 
 impl fmt::Display for HttpErrorKind {
@@ -168,15 +287,57 @@ impl fmt::Display for HttpErrorKind {
             HttpErrorKind::RedirectLoop => write!(f, "redirect loop"),
             HttpErrorKind::TooManyRedirects => write!(f, "too many redirects"),
             HttpErrorKind::RequestTimeout => write!(f, "request timeout"),
+            HttpErrorKind::ConnectTimeout => write!(f, "connect timeout"),
+            HttpErrorKind::ReadTimeout => write!(f, "read timeout"),
             HttpErrorKind::EncodingError => write!(f, "encoding error"),
             HttpErrorKind::DecodingError => write!(f, "decoding error"),
         }
     }
 }
 
+#[cfg(feature = "db")]
+impl crate::ErrorKind {
+    /// Translates a database error kind into the HTTP error a handler should report.
+    ///
+    /// Uses [`DatabaseErrorKind::http_status`](crate::db::DatabaseErrorKind::http_status),
+    /// so e.g. `ConnectionFailed` maps to `ServerError(503)` and `NoRows` to `ClientError(404)`.
+    #[inline]
+    pub fn http_from_database(db: &crate::db::DatabaseErrorKind) -> HttpErrorKind {
+        HttpErrorKind::from_status(db.http_status())
+    }
+}
+
+#[cfg(feature = "db")]
+impl From<crate::db::DatabaseErrorKind> for HttpErrorKind {
+    #[inline]
+    fn from(db: crate::db::DatabaseErrorKind) -> Self {
+        crate::ErrorKind::http_from_database(&db)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{
+        assert_default_status_matches_retryability, assert_lists_every_variant,
+    };
+
+    #[test]
+    fn test_default_status_matches_retryability() {
+        assert_default_status_matches_retryability(
+            HttpErrorKind::all(),
+            HttpErrorKind::default_status,
+            HttpErrorKind::is_retryable,
+        );
+        assert_eq!(
+            HttpErrorKind::ServerError(503).default_status(),
+            ErrorStatus::Temporary
+        );
+        assert_eq!(
+            HttpErrorKind::ClientError(404).default_status(),
+            ErrorStatus::Permanent
+        );
+    }
 
     #[test]
     fn test_client_error_not_retryable() {
@@ -192,6 +353,15 @@ mod tests {
         assert_eq!(kind.status_code(), Some(500));
     }
 
+    #[test]
+    fn test_unsupported_server_errors_not_retryable() {
+        assert!(!HttpErrorKind::ServerError(501).is_retryable());
+        assert!(!HttpErrorKind::ServerError(505).is_retryable());
+        for code in [500, 502, 503, 504] {
+            assert!(HttpErrorKind::ServerError(code).is_retryable(), "{code}");
+        }
+    }
+
     #[test]
     fn test_rate_limited_retryable() {
         let kind = HttpErrorKind::RateLimited;
@@ -199,6 +369,73 @@ mod tests {
         assert_eq!(kind.status_code(), None);
     }
 
+    #[test]
+    fn test_http_status() {
+        assert_eq!(HttpErrorKind::ClientError(404).http_status(), 404);
+        assert_eq!(HttpErrorKind::ServerError(503).http_status(), 503);
+        assert_eq!(HttpErrorKind::RateLimited.http_status(), 429);
+        assert_eq!(HttpErrorKind::NetworkError.http_status(), 502);
+        assert_eq!(
+            crate::ErrorKind::Http(HttpErrorKind::ClientError(410)).http_status(),
+            410
+        );
+    }
+
+    #[test]
+    fn test_all_covers_every_variant() {
+        assert_lists_every_variant(HttpErrorKind::all(), 13, |kind| match kind {
+            HttpErrorKind::ClientError(_) => 0,
+            HttpErrorKind::ServerError(_) => 1,
+            HttpErrorKind::RateLimited => 2,
+            HttpErrorKind::NetworkError => 3,
+            HttpErrorKind::TlsError => 4,
+            HttpErrorKind::InvalidUrl => 5,
+            HttpErrorKind::RedirectLoop => 6,
+            HttpErrorKind::TooManyRedirects => 7,
+            HttpErrorKind::RequestTimeout => 8,
+            HttpErrorKind::EncodingError => 9,
+            HttpErrorKind::DecodingError => 10,
+            HttpErrorKind::ConnectTimeout => 11,
+            HttpErrorKind::ReadTimeout => 12,
+        });
+    }
+
+    #[test]
+    fn test_connect_and_read_timeouts() {
+        for kind in [HttpErrorKind::ConnectTimeout, HttpErrorKind::ReadTimeout] {
+            assert!(kind.is_retryable());
+            assert_eq!(kind.status_code(), None);
+            assert_eq!(kind.http_status(), 504);
+            assert_eq!(kind.severity(), Severity::Warning);
+            assert_eq!(
+                HttpErrorKind::from_machine_string(&kind.to_machine_string()),
+                Some(kind.clone())
+            );
+        }
+        assert_eq!(HttpErrorKind::ConnectTimeout.to_string(), "connect timeout");
+        assert_eq!(HttpErrorKind::ReadTimeout.to_string(), "read timeout");
+        assert_eq!(
+            HttpErrorKind::ConnectTimeout.to_machine_string(),
+            "connect_timeout"
+        );
+        assert_eq!(
+            HttpErrorKind::ReadTimeout.to_machine_string(),
+            "read_timeout"
+        );
+        assert_eq!(
+            HttpErrorKind::ConnectTimeout.status_range_description(),
+            "Connect Timeout"
+        );
+        assert_eq!(
+            HttpErrorKind::ReadTimeout.status_range_description(),
+            "Read Timeout"
+        );
+        assert_eq!(
+            crate::ErrorKind::Http(HttpErrorKind::ConnectTimeout).category(),
+            crate::Category::Network
+        );
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(
@@ -212,4 +449,40 @@ mod tests {
         assert_eq!(HttpErrorKind::RateLimited.to_string(), "rate limited");
         assert_eq!(HttpErrorKind::NetworkError.to_string(), "network error");
     }
+
+    #[cfg(feature = "db")]
+    #[test]
+    fn test_http_from_database() {
+        use crate::ErrorKind;
+        use crate::db::DatabaseErrorKind;
+
+        let cases = [
+            (
+                DatabaseErrorKind::ConnectionFailed,
+                HttpErrorKind::ServerError(503),
+            ),
+            (DatabaseErrorKind::Deadlock, HttpErrorKind::ServerError(503)),
+            (
+                DatabaseErrorKind::ConstraintViolation,
+                HttpErrorKind::ClientError(409),
+            ),
+            (DatabaseErrorKind::NoRows, HttpErrorKind::ClientError(404)),
+            (
+                DatabaseErrorKind::PermissionDenied,
+                HttpErrorKind::ClientError(403),
+            ),
+            (
+                DatabaseErrorKind::TransactionTimeout,
+                HttpErrorKind::ServerError(504),
+            ),
+            (
+                DatabaseErrorKind::QuerySyntax,
+                HttpErrorKind::ServerError(500),
+            ),
+        ];
+        for (db, http) in cases {
+            assert_eq!(ErrorKind::http_from_database(&db), http, "{db:?}");
+            assert_eq!(HttpErrorKind::from(db), http);
+        }
+    }
 }
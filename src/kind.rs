@@ -10,6 +10,80 @@ use super::db::DatabaseErrorKind;
 #[cfg(feature = "storage")]
 use super::storage::StorageErrorKind;
 
+/// How much attention an error deserves, from least to most severe.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Severity {
+    /// Expected outcome worth recording, not worth acting on.
+    Info,
+    /// Caller-side or transient problem.
+    Warning,
+    /// Failure that needs investigation.
+    Error,
+    /// Failure that needs immediate attention.
+    Critical,
+}
+
+impl Severity {
+    /// Returns a machine-readable string representation of this severity.
+    #[inline]
+    pub fn to_machine_string(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+            Severity::Critical => "critical",
+        }
+    }
+}
+
+impl fmt::Display for Severity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_machine_string())
+    }
+}
+
+/// Coarse grouping of error kinds across domains, for dashboards and alerting.
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(rename_all = "snake_case"))]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Category {
+    /// The caller sent something invalid or asked for something missing.
+    Client,
+    /// The service or its infrastructure failed.
+    Server,
+    /// Connectivity or timeouts.
+    Network,
+    /// Data, query or encoding problems.
+    Data,
+    /// Authentication or authorization failures.
+    Auth,
+    /// No better bucket applies.
+    Unknown,
+}
+
+impl Category {
+    /// Returns a machine-readable string representation of this category.
+    #[inline]
+    pub fn to_machine_string(&self) -> &'static str {
+        match self {
+            Category::Client => "client",
+            Category::Server => "server",
+            Category::Network => "network",
+            Category::Data => "data",
+            Category::Auth => "auth",
+            Category::Unknown => "unknown",
+        }
+    }
+}
+
+impl fmt::Display for Category {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.to_machine_string())
+    }
+}
+
 /// Domain-specific error categorization by **action**, not origin.
 ///
 /// Errors are categorized by what the caller should do when encountering them:
@@ -19,7 +93,8 @@ use super::storage::StorageErrorKind;
 /// - Return a specific error to the user
 ///
 /// This is different from `thiserror` which categorizes by where the error came from.
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+///
+/// With the `serde` feature, kinds serialize as their [`ErrorKind::to_machine_string`] form.
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 #[non_exhaustive]
 pub enum ErrorKind {
@@ -53,6 +128,20 @@ pub enum ErrorKind {
 }
 
 impl ErrorKind {
+    /// Returns the core (domain-independent) variants.
+    ///
+    /// Domain kinds are enumerated by their own `all()` functions.
+    #[inline]
+    pub fn all_core() -> &'static [ErrorKind] {
+        &[
+            ErrorKind::NotFound,
+            ErrorKind::PermissionDenied,
+            ErrorKind::Timeout,
+            ErrorKind::Validation,
+            ErrorKind::Unexpected,
+        ]
+    }
+
     /// Returns `true` if this error kind represents a retryable condition.
     ///
     /// Note: This is a default implementation. In production, you may want
@@ -73,6 +162,195 @@ impl ErrorKind {
             ErrorKind::Storage(k) => k.is_retryable(),
         }
     }
+
+    /// Returns the default severity of this error kind.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        match self {
+            ErrorKind::NotFound => Severity::Warning,
+            ErrorKind::PermissionDenied => Severity::Error,
+            ErrorKind::Timeout => Severity::Warning,
+            ErrorKind::Validation => Severity::Warning,
+            ErrorKind::Unexpected => Severity::Error,
+            #[cfg(feature = "http")]
+            ErrorKind::Http(k) => k.severity(),
+            #[cfg(feature = "db")]
+            ErrorKind::Database(k) => k.severity(),
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(k) => k.severity(),
+        }
+    }
+
+    /// Returns a 0–100 score for ranking errors more finely than [`severity`](Self::severity).
+    ///
+    /// Domain kinds score by their severity: 20 for info, 45 for warning, 75 for
+    /// error and 95 for critical.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    ///
+    /// assert!(ErrorKind::Unexpected.severity_score() > ErrorKind::NotFound.severity_score());
+    /// ```
+    pub fn severity_score(&self) -> u8 {
+        match self {
+            ErrorKind::NotFound => 40,
+            ErrorKind::PermissionDenied => 70,
+            ErrorKind::Timeout => 50,
+            ErrorKind::Validation => 30,
+            ErrorKind::Unexpected => 90,
+            #[cfg(any(feature = "http", feature = "db", feature = "storage"))]
+            _ => match self.severity() {
+                Severity::Info => 20,
+                Severity::Warning => 45,
+                Severity::Error => 75,
+                Severity::Critical => 95,
+            },
+        }
+    }
+
+    /// Returns the coarse [`Category`] of this kind, including domain kinds.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::{Category, ErrorKind};
+    ///
+    /// assert_eq!(ErrorKind::Timeout.category(), Category::Network);
+    /// assert_eq!(ErrorKind::PermissionDenied.category(), Category::Auth);
+    /// ```
+    pub fn category(&self) -> Category {
+        match self {
+            ErrorKind::NotFound | ErrorKind::Validation => Category::Client,
+            ErrorKind::PermissionDenied => Category::Auth,
+            ErrorKind::Timeout => Category::Network,
+            ErrorKind::Unexpected => Category::Unknown,
+            #[cfg(feature = "http")]
+            ErrorKind::Http(k) => match k {
+                HttpErrorKind::ClientError(401 | 403) => Category::Auth,
+                HttpErrorKind::ClientError(_)
+                | HttpErrorKind::RateLimited
+                | HttpErrorKind::InvalidUrl
+                | HttpErrorKind::RedirectLoop
+                | HttpErrorKind::TooManyRedirects => Category::Client,
+                HttpErrorKind::ServerError(_) => Category::Server,
+                HttpErrorKind::NetworkError
+                | HttpErrorKind::TlsError
+                | HttpErrorKind::RequestTimeout
+                | HttpErrorKind::ConnectTimeout
+                | HttpErrorKind::ReadTimeout => Category::Network,
+                HttpErrorKind::EncodingError | HttpErrorKind::DecodingError => Category::Data,
+            },
+            #[cfg(feature = "db")]
+            ErrorKind::Database(k) => match k {
+                _ if k.is_connection_error() => Category::Network,
+                DatabaseErrorKind::PermissionDenied => Category::Auth,
+                DatabaseErrorKind::DiskFull | DatabaseErrorKind::ReadOnly => Category::Server,
+                _ => Category::Data,
+            },
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(k) => match k {
+                _ if k.is_network_error() => Category::Network,
+                _ if k.is_permission_error() => Category::Auth,
+                StorageErrorKind::NotFound
+                | StorageErrorKind::DirectoryNotFound
+                | StorageErrorKind::AlreadyExists
+                | StorageErrorKind::IsDirectory
+                | StorageErrorKind::NotDirectory
+                | StorageErrorKind::FileNameTooLong
+                | StorageErrorKind::PathTooLong
+                | StorageErrorKind::InvalidFilename
+                | StorageErrorKind::InvalidPath => Category::Client,
+                _ => Category::Server,
+            },
+        }
+    }
+
+    /// Returns whichever of `a` and `b` should represent both when errors are combined.
+    ///
+    /// The higher [`severity`](ErrorKind::severity) wins. On a tie, server faults
+    /// (an [`http_status`](ErrorKind::http_status) of 500 or above) win over client
+    /// faults; any remaining tie keeps `a`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    ///
+    /// let kind = ErrorKind::dominant(&ErrorKind::NotFound, &ErrorKind::Unexpected);
+    /// assert_eq!(kind, ErrorKind::Unexpected);
+    /// ```
+    pub fn dominant(a: &ErrorKind, b: &ErrorKind) -> ErrorKind {
+        let rank = |kind: &ErrorKind| (kind.severity(), kind.http_status() >= 500);
+        if rank(b) > rank(a) {
+            b.clone()
+        } else {
+            a.clone()
+        }
+    }
+
+    /// Returns the HTTP status code that best represents this error kind.
+    ///
+    /// Domain kinds delegate to their own mapping.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    ///
+    /// assert_eq!(ErrorKind::NotFound.http_status(), 404);
+    /// assert_eq!(ErrorKind::Timeout.http_status(), 504);
+    /// ```
+    #[inline]
+    pub fn http_status(&self) -> u16 {
+        match self {
+            ErrorKind::NotFound => 404,
+            ErrorKind::PermissionDenied => 403,
+            ErrorKind::Timeout => 504,
+            ErrorKind::Validation => 400,
+            ErrorKind::Unexpected => 500,
+            #[cfg(feature = "http")]
+            ErrorKind::Http(k) => k.http_status(),
+            #[cfg(feature = "db")]
+            ErrorKind::Database(k) => k.http_status(),
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(k) => k.http_status(),
+        }
+    }
+}
+
+impl ErrorKind {
+    /// Returns a generic message for this kind that is safe to show to end users.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    ///
+    /// assert_eq!(
+    ///     ErrorKind::NotFound.client_message(),
+    ///     "The requested resource was not found."
+    /// );
+    /// ```
+    #[inline]
+    pub fn client_message(&self) -> &'static str {
+        match self {
+            ErrorKind::NotFound => "The requested resource was not found.",
+            ErrorKind::PermissionDenied => "You do not have permission to perform this action.",
+            ErrorKind::Timeout => "The operation timed out.",
+            ErrorKind::Validation => "The request was invalid.",
+            ErrorKind::Unexpected => "An unexpected error occurred.",
+            #[cfg(feature = "http")]
+            ErrorKind::Http(HttpErrorKind::RateLimited) => "Too many requests.",
+            #[cfg(feature = "http")]
+            ErrorKind::Http(_) => "An upstream service failed.",
+            #[cfg(feature = "db")]
+            ErrorKind::Database(_) => "A database error occurred.",
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(_) => "A storage error occurred.",
+        }
+    }
 }
 
 impl fmt::Display for ErrorKind {
@@ -123,11 +401,93 @@ impl ErrorKind {
             ErrorKind::Storage(k) => Cow::Owned(format!("storage_{}", k.to_machine_string())),
         }
     }
+
+    /// Writes the output of [`ErrorKind::to_machine_string`] without allocating,
+    /// including for domain kinds.
+    pub fn write_machine_string(&self, w: &mut impl fmt::Write) -> fmt::Result {
+        match self {
+            #[cfg(feature = "http")]
+            ErrorKind::Http(k) => {
+                w.write_str("http_")?;
+                k.write_machine_string(w)
+            }
+            #[cfg(feature = "db")]
+            ErrorKind::Database(k) => {
+                w.write_str("database_")?;
+                w.write_str(k.to_machine_string())
+            }
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(k) => {
+                w.write_str("storage_")?;
+                w.write_str(k.to_machine_string())
+            }
+            core => w.write_str(&core.to_machine_string()),
+        }
+    }
+
+    /// Parses the output of [`ErrorKind::to_machine_string`] back into a kind.
+    ///
+    /// Returns `None` for unknown strings or for domain kinds whose feature is disabled.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    ///
+    /// assert_eq!(ErrorKind::from_machine_string("not_found"), Some(ErrorKind::NotFound));
+    /// assert_eq!(ErrorKind::from_machine_string("bogus"), None);
+    /// ```
+    pub fn from_machine_string(s: &str) -> Option<Self> {
+        match s {
+            "not_found" => return Some(ErrorKind::NotFound),
+            "permission_denied" => return Some(ErrorKind::PermissionDenied),
+            "timeout" => return Some(ErrorKind::Timeout),
+            "validation_error" => return Some(ErrorKind::Validation),
+            "unexpected_error" => return Some(ErrorKind::Unexpected),
+            _ => {}
+        }
+        #[cfg(feature = "http")]
+        if let Some(rest) = s.strip_prefix("http_") {
+            return HttpErrorKind::from_machine_string(rest).map(ErrorKind::Http);
+        }
+        #[cfg(feature = "db")]
+        if let Some(rest) = s.strip_prefix("database_") {
+            return DatabaseErrorKind::from_machine_string(rest).map(ErrorKind::Database);
+        }
+        #[cfg(feature = "storage")]
+        if let Some(rest) = s.strip_prefix("storage_") {
+            return StorageErrorKind::from_machine_string(rest).map(ErrorKind::Storage);
+        }
+        None
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for ErrorKind {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&self.to_machine_string())
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for ErrorKind {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        let s: String = serde::Deserialize::deserialize(deserializer)?;
+        Self::from_machine_string(&s)
+            .ok_or_else(|| serde::de::Error::custom(format!("unknown error kind: {s}")))
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::assert_lists_every_variant;
 
     #[test]
     fn test_not_found_is_not_retryable() {
@@ -154,6 +514,196 @@ mod tests {
         assert!(!ErrorKind::Unexpected.is_retryable());
     }
 
+    #[test]
+    fn test_severity_score_defaults() {
+        assert_eq!(ErrorKind::Unexpected.severity_score(), 90);
+        assert_eq!(ErrorKind::PermissionDenied.severity_score(), 70);
+        assert_eq!(ErrorKind::Timeout.severity_score(), 50);
+        assert_eq!(ErrorKind::NotFound.severity_score(), 40);
+        assert_eq!(ErrorKind::Validation.severity_score(), 30);
+        for kind in ErrorKind::all_core() {
+            assert!(kind.severity_score() <= 100);
+        }
+    }
+
+    #[test]
+    fn test_http_status() {
+        assert_eq!(ErrorKind::NotFound.http_status(), 404);
+        assert_eq!(ErrorKind::PermissionDenied.http_status(), 403);
+        assert_eq!(ErrorKind::Timeout.http_status(), 504);
+        assert_eq!(ErrorKind::Validation.http_status(), 400);
+        assert_eq!(ErrorKind::Unexpected.http_status(), 500);
+    }
+
+    #[test]
+    fn test_all_core_covers_every_core_variant() {
+        assert_lists_every_variant(ErrorKind::all_core(), 5, |kind| match kind {
+            ErrorKind::NotFound => 0,
+            ErrorKind::PermissionDenied => 1,
+            ErrorKind::Timeout => 2,
+            ErrorKind::Validation => 3,
+            ErrorKind::Unexpected => 4,
+            #[cfg(feature = "http")]
+            ErrorKind::Http(_) => unreachable!(),
+            #[cfg(feature = "db")]
+            ErrorKind::Database(_) => unreachable!(),
+            #[cfg(feature = "storage")]
+            ErrorKind::Storage(_) => unreachable!(),
+        });
+    }
+
+    #[test]
+    fn test_machine_string_round_trip() {
+        for kind in ErrorKind::all_core() {
+            assert_eq!(
+                ErrorKind::from_machine_string(&kind.to_machine_string()).as_ref(),
+                Some(kind)
+            );
+        }
+    }
+
+    #[test]
+    fn test_write_machine_string_matches() {
+        #[allow(unused_mut)]
+        let mut kinds = ErrorKind::all_core().to_vec();
+        #[cfg(feature = "http")]
+        kinds.extend(HttpErrorKind::all().iter().cloned().map(ErrorKind::Http));
+        #[cfg(feature = "db")]
+        kinds.extend(
+            DatabaseErrorKind::all()
+                .iter()
+                .cloned()
+                .map(ErrorKind::Database),
+        );
+        #[cfg(feature = "storage")]
+        kinds.extend(
+            StorageErrorKind::all()
+                .iter()
+                .cloned()
+                .map(ErrorKind::Storage),
+        );
+
+        for kind in kinds {
+            let mut written = String::new();
+            kind.write_machine_string(&mut written).unwrap();
+            assert_eq!(written, kind.to_machine_string());
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_matches_machine_string() {
+        let json = serde_json::to_string(&ErrorKind::NotFound).unwrap();
+        assert_eq!(json, r#""not_found""#);
+        let kind: ErrorKind = serde_json::from_str(&json).unwrap();
+        assert_eq!(kind, ErrorKind::NotFound);
+        assert!(serde_json::from_str::<ErrorKind>(r#""bogus""#).is_err());
+    }
+
+    #[cfg(feature = "http")]
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_serde_http_kind() {
+        let kind = ErrorKind::Http(HttpErrorKind::ClientError(404));
+        let json = serde_json::to_string(&kind).unwrap();
+        assert_eq!(json, r#""http_client_error_404""#);
+        assert_eq!(serde_json::from_str::<ErrorKind>(&json).unwrap(), kind);
+    }
+
+    #[test]
+    fn test_dominant_prefers_severity() {
+        assert_eq!(
+            ErrorKind::dominant(&ErrorKind::Timeout, &ErrorKind::PermissionDenied),
+            ErrorKind::PermissionDenied
+        );
+        assert_eq!(
+            ErrorKind::dominant(&ErrorKind::Unexpected, &ErrorKind::Validation),
+            ErrorKind::Unexpected
+        );
+    }
+
+    #[test]
+    fn test_dominant_prefers_server_fault_on_tie() {
+        assert_eq!(
+            ErrorKind::NotFound.severity(),
+            ErrorKind::Timeout.severity()
+        );
+        assert_eq!(
+            ErrorKind::dominant(&ErrorKind::NotFound, &ErrorKind::Timeout),
+            ErrorKind::Timeout
+        );
+        assert_eq!(
+            ErrorKind::dominant(&ErrorKind::Timeout, &ErrorKind::NotFound),
+            ErrorKind::Timeout
+        );
+        assert_eq!(
+            ErrorKind::dominant(&ErrorKind::NotFound, &ErrorKind::Validation),
+            ErrorKind::NotFound
+        );
+    }
+
+    #[test]
+    fn test_category_core() {
+        assert_eq!(ErrorKind::NotFound.category(), Category::Client);
+        assert_eq!(ErrorKind::PermissionDenied.category(), Category::Auth);
+        assert_eq!(ErrorKind::Timeout.category(), Category::Network);
+        assert_eq!(ErrorKind::Unexpected.category(), Category::Unknown);
+        assert_eq!(Category::Data.to_string(), "data");
+    }
+
+    #[cfg(all(feature = "http", feature = "db", feature = "storage"))]
+    #[test]
+    fn test_category_domains() {
+        use crate::db::DatabaseErrorKind;
+        use crate::http::HttpErrorKind;
+        use crate::storage::StorageErrorKind;
+
+        let cases = [
+            (
+                ErrorKind::Http(HttpErrorKind::ClientError(404)),
+                Category::Client,
+            ),
+            (
+                ErrorKind::Http(HttpErrorKind::ClientError(401)),
+                Category::Auth,
+            ),
+            (
+                ErrorKind::Http(HttpErrorKind::ServerError(502)),
+                Category::Server,
+            ),
+            (
+                ErrorKind::Http(HttpErrorKind::NetworkError),
+                Category::Network,
+            ),
+            (
+                ErrorKind::Database(DatabaseErrorKind::Deadlock),
+                Category::Data,
+            ),
+            (
+                ErrorKind::Database(DatabaseErrorKind::ConnectionLost),
+                Category::Network,
+            ),
+            (
+                ErrorKind::Storage(StorageErrorKind::DiskFull),
+                Category::Server,
+            ),
+            (
+                ErrorKind::Storage(StorageErrorKind::ReadOnly),
+                Category::Auth,
+            ),
+        ];
+        for (kind, category) in cases {
+            assert_eq!(kind.category(), category, "{kind:?}");
+        }
+    }
+
+    #[test]
+    fn test_severity_order() {
+        assert!(Severity::Info < Severity::Warning);
+        assert!(Severity::Warning < Severity::Error);
+        assert!(Severity::Error < Severity::Critical);
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(ErrorKind::NotFound.to_string(), "not found");
@@ -72,6 +72,16 @@ pub mod kind;
 /// Explicit retry semantics for errors.
 pub mod status;
 
+/// Conversions from `Option` into kinded errors.
+pub mod option;
+
+/// Visitor-based access to error fields for custom output formats.
+pub mod visit;
+
+/// Support code for the error construction macros.
+#[doc(hidden)]
+pub mod macros;
+
 /// Common imports for using erract.
 pub mod prelude;
 
@@ -92,13 +102,28 @@ pub mod arena;
 #[cfg(feature = "storage")]
 pub mod storage;
 
-pub use crate::context::AddContext;
-pub use crate::error::{Error, ErrorBuilder};
+#[cfg(test)]
+mod test_util;
+
+pub use crate::context::{AddContext, ErrorContext};
+pub use crate::convert::MapErrToExt;
+pub use crate::error::{
+    BoxError, DisplayOptions, Error, ErrorBuilder, ErrorDisplay, ErrorFields, ExnError,
+    KeySanitizer, TraceContext, set_context_key_sanitizer, set_max_context,
+    set_max_context_value_len, set_min_log_severity, set_strict_context_keys,
+};
 pub use crate::extract::{
-    count_errors, count_frames, has_permanent, has_retryable, is_all_retryable,
+    count_errors, count_frames, display_chain, has_fatal, has_permanent, has_retryable,
+    is_all_retryable, most_severe,
+};
+pub use crate::kind::{Category, ErrorKind, Severity};
+pub use crate::status::{
+    ErrorStatus, Jitter, RetryDecision, RetryExt, RetryPolicy, jittered_delay, retry_with,
+    with_retry_boundary,
 };
-pub use crate::kind::ErrorKind;
-pub use crate::status::ErrorStatus;
+pub use crate::visit::ErrorVisitor;
+#[cfg(feature = "derive")]
+pub use erract_derive::ErrorContext;
 
 // Re-export exn for convenience
 pub use exn;
@@ -0,0 +1,136 @@
+use std::borrow::Cow;
+use std::fmt;
+
+/// Constructs an [`Error`](crate::Error) from a kind, a format string and optional context.
+///
+/// The error is permanent unless the kind is prefixed with `temporary` or
+/// `persistent`. Trailing `key = value` pairs are attached as context via
+/// [`Error::with_context_value`](crate::Error::with_context_value) rather than
+/// being passed to the formatter.
+///
+/// # Examples
+///
+/// ```
+/// use erract::prelude::*;
+///
+/// let id = 42;
+///
+/// let error = err!(NotFound, "user {} not found", id);
+/// assert_eq!(error.kind(), &ErrorKind::NotFound);
+/// assert!(error.is_permanent());
+/// assert_eq!(error.message(), "user 42 not found");
+///
+/// let error = err!(temporary Timeout, "upstream timed out");
+/// assert!(error.is_retryable());
+///
+/// let error = err!(NotFound, "user not found", user_id = id, op = "lookup");
+/// assert_eq!(error.context()[0], ("user_id".into(), "42".into()));
+/// assert_eq!(error.context()[1], ("op".into(), "lookup".into()));
+///
+/// let error = err!(permanent Validation, "bad id {}", id + 1, field = "id");
+/// assert_eq!(error.message(), "bad id 43");
+/// assert_eq!(error.context().len(), 1);
+/// ```
+#[macro_export]
+macro_rules! err {
+    (temporary $kind:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__err_args!(Temporary $kind $fmt [] $($rest)*)
+    };
+    (persistent $kind:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__err_args!(Persistent $kind $fmt [] $($rest)*)
+    };
+    (permanent $kind:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__err_args!(Permanent $kind $fmt [] $($rest)*)
+    };
+    ($kind:ident, $fmt:literal $($rest:tt)*) => {
+        $crate::__err_args!(Permanent $kind $fmt [] $($rest)*)
+    };
+}
+
+/// Splits format arguments from trailing `key = value` context pairs.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! __err_args {
+    ($status:ident $kind:ident $fmt:literal [$($args:tt)*] $(,)?) => {
+        $crate::Error::new(
+            $crate::ErrorKind::$kind,
+            $crate::ErrorStatus::$status,
+            $crate::macros::message(::std::format_args!($fmt $($args)*)),
+        )
+    };
+    ($status:ident $kind:ident $fmt:literal [$($args:tt)*]
+        $(, $key:ident = $value:expr)+ $(,)?) => {
+        $crate::Error::new(
+            $crate::ErrorKind::$kind,
+            $crate::ErrorStatus::$status,
+            $crate::macros::message(::std::format_args!($fmt $($args)*)),
+        )
+        $(.with_context_value(::std::stringify!($key), $value))+
+    };
+    ($status:ident $kind:ident $fmt:literal [$($args:tt)*] , $arg:expr $(, $($rest:tt)*)?) => {
+        $crate::__err_args!($status $kind $fmt [$($args)* , $arg] $(, $($rest)*)?)
+    };
+}
+
+/// Attaches `key => value` context to a `Result` inline, without breaking a `?` chain.
+///
+/// Values are converted with [`ToString`]. Works on `Result<T, Error>` and on
+/// `erract::Result<T>`; for the latter a [context frame](crate::Error::is_context_frame)
+/// holding only the new pairs is raised with `or_raise`, leaving the kind and
+/// message to the wrapped error (see [`AddContextIter`](crate::context::AddContextIter)).
+///
+/// # Examples
+///
+/// ```
+/// use erract::prelude::*;
+///
+/// fn fetch(id: u32) -> Result<String, Error> {
+///     Err(Error::not_found())
+/// }
+///
+/// fn load(id: u32) -> Result<String, Error> {
+///     let user = try_context!(fetch(id), "user_id" => id, "op" => "fetch")?;
+///     Ok(user)
+/// }
+///
+/// let err = load(7).unwrap_err();
+/// assert_eq!(err.context()[0], ("user_id".into(), "7".into()));
+/// assert_eq!(err.context()[1], ("op".into(), "fetch".into()));
+/// ```
+///
+/// With an error tree:
+///
+/// ```
+/// use erract::prelude::*;
+///
+/// fn fetch(id: u32) -> erract::Result<String> {
+///     Err(Error::not_found().raise())
+/// }
+///
+/// fn load(id: u32) -> erract::Result<String> {
+///     let user = try_context!(fetch(id), "user_id" => id)?;
+///     Ok(user)
+/// }
+///
+/// let exn = load(7).unwrap_err();
+/// assert_eq!(exn.as_error().context()[0], ("user_id".into(), "7".into()));
+/// ```
+#[macro_export]
+macro_rules! try_context {
+    ($result:expr, $($key:expr => $value:expr),+ $(,)?) => {
+        $crate::context::AddContextIter::with_context_iter(
+            $result,
+            [$(($key, ::std::string::ToString::to_string(&$value))),+],
+        )
+    };
+}
+
+/// Turns formatted arguments into a message, borrowing when there is nothing to format.
+#[doc(hidden)]
+#[inline]
+pub fn message(args: fmt::Arguments<'_>) -> Cow<'static, str> {
+    match args.as_str() {
+        Some(s) => Cow::Borrowed(s),
+        None => Cow::Owned(fmt::format(args)),
+    }
+}
@@ -0,0 +1,83 @@
+use std::borrow::Cow;
+
+use crate::{Error, ErrorKind, ErrorStatus};
+
+/// Extension trait turning `Option<T>` into an `erract::Result<T>` with a kinded error.
+///
+/// The error is raised at the caller's location.
+///
+/// # Examples
+///
+/// ```
+/// use erract::prelude::*;
+///
+/// fn find(id: u32) -> Option<&'static str> {
+///     (id == 1).then_some("alice")
+/// }
+///
+/// assert_eq!(find(1).or_not_found("user not found").unwrap(), "alice");
+///
+/// let exn = find(2).or_not_found("user not found").unwrap_err();
+/// assert_eq!(exn.as_error().kind(), &ErrorKind::NotFound);
+/// ```
+pub trait OptionExt<T> {
+    /// Raises a permanent `NotFound` error with `message` if the option is `None`.
+    fn or_not_found(self, message: impl Into<Cow<'static, str>>) -> crate::Result<T>;
+
+    /// Raises an error of the given kind and status with `message` if the option is `None`.
+    fn or_kind(
+        self,
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: impl Into<Cow<'static, str>>,
+    ) -> crate::Result<T>;
+}
+
+impl<T> OptionExt<T> for Option<T> {
+    #[inline]
+    #[track_caller]
+    fn or_not_found(self, message: impl Into<Cow<'static, str>>) -> crate::Result<T> {
+        self.or_kind(ErrorKind::NotFound, ErrorStatus::Permanent, message)
+    }
+
+    #[inline]
+    #[track_caller]
+    fn or_kind(
+        self,
+        kind: ErrorKind,
+        status: ErrorStatus,
+        message: impl Into<Cow<'static, str>>,
+    ) -> crate::Result<T> {
+        match self {
+            Some(value) => Ok(value),
+            None => Err(Error::new(kind, status, message).raise()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_some_passes_through() {
+        assert_eq!(Some(3).or_not_found("missing").unwrap(), 3);
+        let value = Some("x").or_kind(ErrorKind::Timeout, ErrorStatus::Temporary, "slow");
+        assert_eq!(value.unwrap(), "x");
+    }
+
+    #[test]
+    fn test_none_raises_kind() {
+        let exn = None::<u32>.or_not_found("user missing").unwrap_err();
+        assert_eq!(exn.as_error().kind(), &ErrorKind::NotFound);
+        assert!(exn.as_error().is_permanent());
+        assert_eq!(exn.as_error().message(), "user missing");
+        assert_eq!(exn.as_frame().location().file(), file!());
+
+        let exn = None::<u32>
+            .or_kind(ErrorKind::Timeout, ErrorStatus::Temporary, "slow")
+            .unwrap_err();
+        assert_eq!(exn.as_error().kind(), &ErrorKind::Timeout);
+        assert!(exn.as_error().is_retryable());
+    }
+}
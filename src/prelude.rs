@@ -15,8 +15,21 @@
 //! }
 //! ```
 pub use crate::context::AddContext;
+pub use crate::convert::MapErrToExt;
 pub use crate::error::{Error, ErrorBuilder};
-pub use crate::extract::{count_errors, count_frames};
-pub use crate::kind::ErrorKind;
-pub use crate::status::ErrorStatus;
+pub use crate::extract::{
+    InspectErrorExt, count_errors, count_frames, display_chain, has_permanent, has_retryable,
+    is_all_retryable, root_cause,
+};
+pub use crate::kind::{ErrorKind, Severity};
+pub use crate::option::OptionExt;
+pub use crate::status::{ErrorStatus, RetryExt, RetryPolicy, retry_with};
+pub use crate::{err, try_context};
 pub use exn::{ResultExt, bail, ensure};
+
+#[cfg(feature = "db")]
+pub use crate::db::DatabaseErrorKind;
+#[cfg(feature = "http")]
+pub use crate::http::HttpErrorKind;
+#[cfg(feature = "storage")]
+pub use crate::storage::StorageErrorKind;
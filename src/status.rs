@@ -1,4 +1,8 @@
+use std::borrow::Cow;
 use std::fmt;
+use std::time::{Duration, Instant};
+
+use crate::{Error, ErrorKind};
 
 /// Explicit retry semantics for errors.
 ///
@@ -37,6 +41,44 @@ impl ErrorStatus {
     pub fn is_persistent(&self) -> bool {
         matches!(self, ErrorStatus::Persistent)
     }
+
+    /// Merges two statuses; the result is retryable only if both are.
+    ///
+    /// Otherwise the more terminal status wins (`Permanent` over `Persistent` over
+    /// `Temporary`). The operation is associative and commutative with `Temporary`
+    /// as identity, so `iter.fold(ErrorStatus::Temporary, ErrorStatus::combine)`
+    /// is permanent as soon as any element is.
+    #[inline]
+    pub fn combine(self, other: ErrorStatus) -> ErrorStatus {
+        if other.rank() > self.rank() {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Orders statuses from most to least retryable.
+    #[inline]
+    pub(crate) fn rank(&self) -> u8 {
+        match self {
+            ErrorStatus::Temporary => 0,
+            ErrorStatus::Persistent => 1,
+            ErrorStatus::Permanent => 2,
+        }
+    }
+
+    /// Maps a boolean retry flag to `Temporary` (`true`) or `Permanent` (`false`).
+    ///
+    /// This is the inverse of `From<ErrorStatus> for bool`; `Persistent` cannot be
+    /// produced this way since a flag does not say whether retries already happened.
+    #[inline]
+    pub fn from_retryable(retryable: bool) -> ErrorStatus {
+        if retryable {
+            ErrorStatus::Temporary
+        } else {
+            ErrorStatus::Permanent
+        }
+    }
 }
 
 impl fmt::Display for ErrorStatus {
@@ -56,6 +98,13 @@ impl From<ErrorStatus> for bool {
     }
 }
 
+impl From<bool> for ErrorStatus {
+    #[inline]
+    fn from(retryable: bool) -> Self {
+        ErrorStatus::from_retryable(retryable)
+    }
+}
+
 impl ErrorStatus {
     /// Returns a machine-readable string representation of this error status.
     ///
@@ -78,9 +127,423 @@ impl ErrorStatus {
     }
 }
 
+/// Exponential backoff configuration for [`retry_with`].
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use erract::status::RetryPolicy;
+///
+/// let policy = RetryPolicy::new(3).with_base_delay(Duration::from_millis(10));
+/// assert_eq!(policy.next_delay(1), Some(Duration::from_millis(10)));
+/// assert_eq!(policy.next_delay(2), Some(Duration::from_millis(20)));
+/// assert_eq!(policy.next_delay(3), None);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RetryPolicy {
+    max_attempts: u32,
+    base_delay: Duration,
+    max_delay: Duration,
+    jitter: Jitter,
+    seed: u64,
+    deadline: Option<Instant>,
+}
+
+/// Why a retry driver stopped, recorded on the final error for later audit.
+///
+/// [`retry_with`] stamps this via [`Error::with_retry_decision`].
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(tag = "outcome", rename_all = "snake_case"))]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum RetryDecision {
+    /// Retried, then failed with an error that was not retryable.
+    Retried {
+        /// Total attempts made, including the first.
+        attempts: u32,
+    },
+    /// Still retryable, but the driver stopped, e.g. because the budget was spent.
+    GaveUp {
+        /// Why the driver stopped.
+        reason: Cow<'static, str>,
+    },
+    /// The first failure was not retryable, so no retry was attempted.
+    NotRetryable,
+}
+
+impl RetryDecision {
+    /// Returns a machine-readable string representation of this decision.
+    #[inline]
+    pub fn to_machine_string(&self) -> &'static str {
+        match self {
+            RetryDecision::Retried { .. } => "retried",
+            RetryDecision::GaveUp { .. } => "gave_up",
+            RetryDecision::NotRetryable => "not_retryable",
+        }
+    }
+}
+
+impl fmt::Display for RetryDecision {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            RetryDecision::Retried { attempts } => write!(f, "retried {attempts} times"),
+            RetryDecision::GaveUp { reason } => write!(f, "gave up: {reason}"),
+            RetryDecision::NotRetryable => f.write_str("not retryable"),
+        }
+    }
+}
+
+/// How [`RetryPolicy`] spreads delays around the exponential backoff.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum Jitter {
+    /// Use the exact exponential delay.
+    #[default]
+    None,
+    /// Pick a delay anywhere in `[0, delay]`.
+    Full,
+    /// Keep half the delay and pick the other half in `[0, delay / 2]`.
+    Equal,
+}
+
+/// Reads and parses `{prefix}_{name}`, returning `None` if it is unset or invalid.
+fn env_value<T>(prefix: &str, name: &str, parse: impl FnOnce(&str) -> Option<T>) -> Option<T> {
+    let var = format!("{prefix}_{name}");
+    let value = std::env::var(&var).ok()?;
+    let parsed = parse(value.trim());
+    #[cfg(feature = "log")]
+    if parsed.is_none() {
+        log::warn!("ignoring invalid {var}={value:?}, using the default");
+    }
+    parsed
+}
+
+/// Returns a pseudo-random delay in `[0, base * 2^attempt]`.
+///
+/// The result depends only on the arguments, so a fixed `seed` (for example a
+/// hash of a request ID) yields reproducible backoff.
+///
+/// # Examples
+///
+/// ```
+/// use std::time::Duration;
+/// use erract::status::jittered_delay;
+///
+/// let base = Duration::from_millis(100);
+/// let delay = jittered_delay(base, 2, 42);
+/// assert!(delay <= base * 4);
+/// assert_eq!(delay, jittered_delay(base, 2, 42));
+/// ```
+pub fn jittered_delay(base: Duration, attempt: u32, seed: u64) -> Duration {
+    let factor = 1u32.checked_shl(attempt).unwrap_or(u32::MAX);
+    spread(base.saturating_mul(factor), seed, attempt)
+}
+
+/// Scales `delay` by a xorshift draw in `[0, 1]` derived from `seed` and `attempt`.
+fn spread(delay: Duration, seed: u64, attempt: u32) -> Duration {
+    let mut x = seed ^ 0x9E37_79B9_7F4A_7C15 ^ (u64::from(attempt) << 32);
+    if x == 0 {
+        x = 0x9E37_79B9_7F4A_7C15;
+    }
+    x ^= x << 13;
+    x ^= x >> 7;
+    x ^= x << 17;
+    let nanos = delay.as_nanos().saturating_mul(u128::from(x)) / u128::from(u64::MAX);
+    Duration::from_nanos(u64::try_from(nanos).unwrap_or(u64::MAX))
+}
+
+impl RetryPolicy {
+    /// Creates a policy allowing up to `max_attempts` attempts in total.
+    #[inline]
+    pub fn new(max_attempts: u32) -> Self {
+        RetryPolicy {
+            max_attempts,
+            base_delay: Duration::from_millis(100),
+            max_delay: Duration::from_secs(10),
+            jitter: Jitter::None,
+            seed: 0,
+            deadline: None,
+        }
+    }
+
+    /// Builds a policy from `{prefix}_MAX_ATTEMPTS`, `{prefix}_BASE_DELAY_MS`,
+    /// `{prefix}_MAX_DELAY_MS` and `{prefix}_JITTER` (`none`, `full` or `equal`).
+    ///
+    /// Missing or invalid variables keep the [`Default`] values; with the `log`
+    /// feature, invalid ones are reported as warnings.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::RetryPolicy;
+    ///
+    /// // With none of the variables set, this is the default policy.
+    /// assert_eq!(RetryPolicy::from_env("ERRACT_DOC_RETRY"), RetryPolicy::default());
+    /// ```
+    pub fn from_env(prefix: &str) -> Self {
+        let mut policy = RetryPolicy::default();
+        if let Some(max_attempts) = env_value(prefix, "MAX_ATTEMPTS", |s| s.parse().ok()) {
+            policy.max_attempts = max_attempts;
+        }
+        if let Some(ms) = env_value(prefix, "BASE_DELAY_MS", |s| s.parse().ok()) {
+            policy.base_delay = Duration::from_millis(ms);
+        }
+        if let Some(ms) = env_value(prefix, "MAX_DELAY_MS", |s| s.parse().ok()) {
+            policy.max_delay = Duration::from_millis(ms);
+        }
+        if let Some(jitter) = env_value(prefix, "JITTER", |s| {
+            match s.to_ascii_lowercase().as_str() {
+                "none" => Some(Jitter::None),
+                "full" => Some(Jitter::Full),
+                "equal" => Some(Jitter::Equal),
+                _ => None,
+            }
+        }) {
+            policy.jitter = jitter;
+        }
+        policy
+    }
+
+    /// Sets the delay before the first retry.
+    #[inline]
+    #[must_use]
+    pub fn with_base_delay(mut self, delay: Duration) -> Self {
+        self.base_delay = delay;
+        self
+    }
+
+    /// Sets the upper bound for any single delay.
+    #[inline]
+    #[must_use]
+    pub fn with_max_delay(mut self, delay: Duration) -> Self {
+        self.max_delay = delay;
+        self
+    }
+
+    /// Sets how delays are randomized.
+    #[inline]
+    #[must_use]
+    pub fn with_jitter(mut self, jitter: Jitter) -> Self {
+        self.jitter = jitter;
+        self
+    }
+
+    /// Sets the seed for jittered delays.
+    #[inline]
+    #[must_use]
+    pub fn with_seed(mut self, seed: u64) -> Self {
+        self.seed = seed;
+        self
+    }
+
+    /// Stops retrying once the next attempt would start after `deadline`.
+    ///
+    /// The attempt budget still applies; whichever runs out first ends the retries.
+    #[inline]
+    #[must_use]
+    pub fn with_deadline(mut self, deadline: Instant) -> Self {
+        self.deadline = Some(deadline);
+        self
+    }
+
+    /// Returns the maximum number of attempts.
+    #[inline]
+    pub fn max_attempts(&self) -> u32 {
+        self.max_attempts
+    }
+
+    /// Returns the delay before the first retry.
+    #[inline]
+    pub fn base_delay(&self) -> Duration {
+        self.base_delay
+    }
+
+    /// Returns the upper bound for any single delay.
+    #[inline]
+    pub fn max_delay(&self) -> Duration {
+        self.max_delay
+    }
+
+    /// Returns how delays are randomized.
+    #[inline]
+    pub fn jitter(&self) -> Jitter {
+        self.jitter
+    }
+
+    /// Returns the deadline, if one is set.
+    #[inline]
+    pub fn deadline(&self) -> Option<Instant> {
+        self.deadline
+    }
+
+    /// Returns the delay to wait after `attempt` failed attempts, or `None` once
+    /// the attempt budget is spent or the next attempt would miss the deadline.
+    pub fn next_delay(&self, attempt: u32) -> Option<Duration> {
+        if attempt == 0 || attempt >= self.max_attempts {
+            return None;
+        }
+        self.delay_before_deadline(attempt)
+    }
+
+    /// Returns the attempt budget for errors of `kind`.
+    ///
+    /// Database kinds with a [`max_retries`](crate::db::DatabaseErrorKind::max_retries)
+    /// cap override the policy's `max_attempts`.
+    pub fn max_attempts_for(&self, kind: &ErrorKind) -> u32 {
+        match kind {
+            #[cfg(feature = "db")]
+            ErrorKind::Database(k) => k
+                .max_retries()
+                .map_or(self.max_attempts, |n| n.saturating_add(1)),
+            _ => self.max_attempts,
+        }
+    }
+
+    /// Returns the backoff for `attempt` unless waiting it would pass the deadline.
+    fn delay_before_deadline(&self, attempt: u32) -> Option<Duration> {
+        let delay = self.backoff(attempt);
+        match self.deadline {
+            Some(deadline) => Instant::now()
+                .checked_add(delay)
+                .is_some_and(|next| next <= deadline)
+                .then_some(delay),
+            None => Some(delay),
+        }
+    }
+
+    fn backoff(&self, attempt: u32) -> Duration {
+        let factor = 1u32.checked_shl(attempt - 1).unwrap_or(u32::MAX);
+        let delay = self.base_delay.saturating_mul(factor).min(self.max_delay);
+        match self.jitter {
+            Jitter::None => delay,
+            Jitter::Full => spread(delay, self.seed, attempt),
+            Jitter::Equal => delay / 2 + spread(delay - delay / 2, self.seed, attempt),
+        }
+    }
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        RetryPolicy::new(3)
+    }
+}
+
+/// Runs `f` until it succeeds, fails with a non-retryable error, or the
+/// policy's attempt budget or deadline is spent.
+///
+/// The budget is taken from [`RetryPolicy::max_attempts_for`] using the kind of
+/// each failure. The returned error has its [`Error::attempt`] set to the
+/// attempt that produced it and its [`Error::retry_decision`] explaining why
+/// the driver stopped. An error that is still retryable when the budget runs
+/// out is returned [`Error::mark_exhausted`].
+pub fn retry_with<T>(
+    policy: &RetryPolicy,
+    mut f: impl FnMut() -> Result<T, Error>,
+) -> Result<T, Error> {
+    let mut attempt = 1;
+    loop {
+        let err = match f() {
+            Ok(value) => return Ok(value),
+            Err(err) => err,
+        };
+        if !err.is_retryable() {
+            let decision = if attempt == 1 {
+                RetryDecision::NotRetryable
+            } else {
+                RetryDecision::Retried { attempts: attempt }
+            };
+            return Err(err.with_attempt(attempt).with_retry_decision(decision));
+        }
+        let max_attempts = policy.max_attempts_for(err.kind());
+        let reason = if attempt >= max_attempts {
+            format!("exhausted {max_attempts} attempts")
+        } else if let Some(delay) = policy.delay_before_deadline(attempt) {
+            std::thread::sleep(delay);
+            attempt += 1;
+            continue;
+        } else {
+            format!("deadline reached after {attempt} attempts")
+        };
+        return Err(err
+            .mark_exhausted()
+            .with_attempt(attempt)
+            .with_retry_decision(RetryDecision::GaveUp {
+                reason: reason.into(),
+            }));
+    }
+}
+
+/// Runs `f` as the last retry layer: temporary errors escaping it become persistent.
+///
+/// Every temporary frame in the returned tree is [`Error::mark_exhausted`], so
+/// callers further up do not retry again. Trees with no temporary frame are
+/// returned untouched; otherwise the tree is rebuilt with
+/// [`map_errors`](crate::extract::map_errors) and its frames record the caller's location.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, status::with_retry_boundary};
+///
+/// let exn = with_retry_boundary(|| -> erract::Result<()> { Err(Error::timeout().raise()) }).unwrap_err();
+/// assert!(exn.as_error().status().is_persistent());
+/// ```
+#[track_caller]
+pub fn with_retry_boundary<T>(f: impl FnOnce() -> crate::Result<T>) -> crate::Result<T> {
+    match f() {
+        Err(exn) if crate::extract::has_retryable(&exn) => {
+            Err(crate::extract::map_errors(exn, |error| {
+                if error.is_retryable() {
+                    error.mark_exhausted()
+                } else {
+                    error
+                }
+            }))
+        }
+        result => result,
+    }
+}
+
+/// Retry combinators for `erract::Result`.
+pub trait RetryExt<T>: Sized {
+    /// Re-runs `f` while the whole error tree is retryable and the policy allows it.
+    ///
+    /// `self` is the outcome of the first attempt. Unlike checking
+    /// [`has_retryable`](crate::extract::has_retryable), a single permanent
+    /// frame anywhere in the tree stops retrying immediately
+    /// (see [`is_all_retryable`](crate::extract::is_all_retryable)).
+    fn retry_if_all_retryable<F>(self, policy: &RetryPolicy, f: F) -> Self
+    where
+        F: FnMut() -> Self;
+}
+
+impl<T> RetryExt<T> for crate::Result<T> {
+    fn retry_if_all_retryable<F>(self, policy: &RetryPolicy, mut f: F) -> Self
+    where
+        F: FnMut() -> Self,
+    {
+        let mut result = self;
+        let mut attempt = 1;
+        while let Err(exn) = &result {
+            if !crate::extract::is_all_retryable(exn) {
+                break;
+            }
+            let Some(delay) = policy.next_delay(attempt) else {
+                break;
+            };
+            std::thread::sleep(delay);
+            result = f();
+            attempt += 1;
+        }
+        result
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::ErrorKind;
 
     #[test]
     fn test_permanent_is_not_retryable() {
@@ -112,4 +575,276 @@ mod tests {
         assert_eq!(ErrorStatus::Temporary.to_string(), "temporary");
         assert_eq!(ErrorStatus::Persistent.to_string(), "persistent");
     }
+
+    #[test]
+    fn test_combine() {
+        use ErrorStatus::*;
+
+        let cases = [
+            (Temporary, Temporary, Temporary),
+            (Temporary, Persistent, Persistent),
+            (Temporary, Permanent, Permanent),
+            (Persistent, Temporary, Persistent),
+            (Persistent, Persistent, Persistent),
+            (Persistent, Permanent, Permanent),
+            (Permanent, Temporary, Permanent),
+            (Permanent, Persistent, Permanent),
+            (Permanent, Permanent, Permanent),
+        ];
+        for (a, b, expected) in cases {
+            assert_eq!(a.clone().combine(b.clone()), expected, "{a:?} + {b:?}");
+        }
+
+        let statuses = [Temporary, Permanent, Temporary];
+        assert_eq!(
+            statuses.into_iter().fold(Temporary, ErrorStatus::combine),
+            Permanent
+        );
+    }
+
+    #[test]
+    fn test_from_retryable() {
+        assert_eq!(ErrorStatus::from_retryable(true), ErrorStatus::Temporary);
+        assert_eq!(ErrorStatus::from_retryable(false), ErrorStatus::Permanent);
+        assert_eq!(ErrorStatus::from(true), ErrorStatus::Temporary);
+        assert_eq!(ErrorStatus::from(false), ErrorStatus::Permanent);
+        for flag in [true, false] {
+            assert_eq!(bool::from(ErrorStatus::from(flag)), flag);
+        }
+    }
+
+    #[test]
+    fn test_next_delay_is_capped() {
+        let policy = RetryPolicy::new(10)
+            .with_base_delay(Duration::from_millis(100))
+            .with_max_delay(Duration::from_millis(300));
+        assert_eq!(policy.next_delay(0), None);
+        assert_eq!(policy.next_delay(1), Some(Duration::from_millis(100)));
+        assert_eq!(policy.next_delay(2), Some(Duration::from_millis(200)));
+        assert_eq!(policy.next_delay(3), Some(Duration::from_millis(300)));
+        assert_eq!(policy.next_delay(10), None);
+    }
+
+    #[test]
+    fn test_jittered_delay_bounds() {
+        let base = Duration::from_millis(50);
+        for seed in 0..200 {
+            for attempt in 0..6 {
+                assert!(jittered_delay(base, attempt, seed) <= base * (1 << attempt));
+            }
+        }
+        assert_eq!(jittered_delay(base, 3, 7), jittered_delay(base, 3, 7));
+        assert_ne!(jittered_delay(base, 3, 7), jittered_delay(base, 3, 8));
+    }
+
+    #[test]
+    fn test_policy_jitter() {
+        let base = Duration::from_millis(100);
+        let exact = RetryPolicy::new(5).with_base_delay(base);
+        assert_eq!(exact.jitter(), Jitter::None);
+        assert_eq!(exact.next_delay(3), Some(base * 4));
+
+        for seed in 0..100 {
+            let full = exact.clone().with_jitter(Jitter::Full).with_seed(seed);
+            let delay = full.next_delay(3).unwrap();
+            assert!(delay <= base * 4);
+            assert_eq!(full.next_delay(3), Some(delay));
+
+            let equal = exact.clone().with_jitter(Jitter::Equal).with_seed(seed);
+            let delay = equal.next_delay(3).unwrap();
+            assert!(delay >= base * 2 && delay <= base * 4);
+        }
+    }
+
+    #[test]
+    fn test_retry_with_sets_attempt() {
+        let policy = RetryPolicy::new(4).with_base_delay(Duration::ZERO);
+        let mut calls = 0;
+        let err = retry_with(&policy, || -> Result<(), Error> {
+            calls += 1;
+            Err(Error::timeout())
+        })
+        .unwrap_err();
+        assert_eq!(calls, 4);
+        assert_eq!(err.attempt(), Some(4));
+        assert!(err.status().is_persistent());
+        assert!(!crate::has_retryable(&err.clone().raise()));
+        assert_eq!(
+            err.retry_decision(),
+            Some(&RetryDecision::GaveUp {
+                reason: "exhausted 4 attempts".into()
+            })
+        );
+        assert!(
+            err.to_json().contains(
+                r#""retry_decision":{"outcome":"gave_up","reason":"exhausted 4 attempts"}"#
+            )
+        );
+    }
+
+    #[test]
+    fn test_with_retry_boundary_escalates() {
+        fn fetch() -> crate::Result<()> {
+            Err(Error::timeout()
+                .raise()
+                .raise(Error::temporary(ErrorKind::Unexpected, "fetch failed")))
+        }
+
+        let exn = with_retry_boundary(fetch).unwrap_err();
+        assert!(!crate::has_retryable(&exn));
+        assert_eq!(crate::count_frames(&exn), 2);
+        for error in crate::extract::causes(&exn) {
+            assert_eq!(error.status(), &ErrorStatus::Persistent);
+        }
+
+        let exn = with_retry_boundary(|| -> crate::Result<()> { Err(Error::not_found().raise()) })
+            .unwrap_err();
+        assert!(exn.as_error().is_permanent());
+        assert!(exn.as_error().context().is_empty());
+        assert_eq!(with_retry_boundary(|| Ok(3)).unwrap(), 3);
+    }
+
+    #[test]
+    fn test_retry_with_deadline_stops_early() {
+        let policy = RetryPolicy::new(100)
+            .with_base_delay(Duration::from_millis(10))
+            .with_max_delay(Duration::from_millis(10))
+            .with_deadline(Instant::now() + Duration::from_millis(35));
+        let mut calls = 0;
+        let err = retry_with(&policy, || -> Result<(), Error> {
+            calls += 1;
+            Err(Error::timeout())
+        })
+        .unwrap_err();
+        assert!((1..=4).contains(&calls), "{calls} calls");
+        assert_eq!(
+            err.retry_decision(),
+            Some(&RetryDecision::GaveUp {
+                reason: format!("deadline reached after {calls} attempts").into()
+            })
+        );
+        assert!(err.status().is_persistent());
+
+        let expired = RetryPolicy::new(5).with_deadline(Instant::now());
+        assert_eq!(expired.next_delay(1), None);
+        let open = RetryPolicy::new(5).with_deadline(Instant::now() + Duration::from_secs(60));
+        assert_eq!(open.next_delay(1), Some(open.base_delay()));
+        assert_eq!(open.next_delay(5), None);
+    }
+
+    #[test]
+    fn test_retry_with_not_retryable_decision() {
+        let policy = RetryPolicy::new(4).with_base_delay(Duration::ZERO);
+        let err =
+            retry_with(&policy, || -> Result<(), Error> { Err(Error::not_found()) }).unwrap_err();
+        assert_eq!(err.retry_decision(), Some(&RetryDecision::NotRetryable));
+        assert!(
+            err.to_json()
+                .contains(r#""retry_decision":{"outcome":"not_retryable"}"#)
+        );
+    }
+
+    #[test]
+    fn test_retry_with_stops_on_permanent() {
+        let policy = RetryPolicy::new(4).with_base_delay(Duration::ZERO);
+        let mut calls = 0;
+        let err = retry_with(&policy, || -> Result<(), Error> {
+            calls += 1;
+            if calls < 2 {
+                Err(Error::timeout())
+            } else {
+                Err(Error::permanent(ErrorKind::NotFound, "gone"))
+            }
+        })
+        .unwrap_err();
+        assert_eq!(calls, 2);
+        assert_eq!(err.attempt(), Some(2));
+        assert_eq!(
+            err.retry_decision(),
+            Some(&RetryDecision::Retried { attempts: 2 })
+        );
+    }
+
+    #[cfg(feature = "db")]
+    #[test]
+    fn test_db_kinds_override_max_attempts() {
+        use crate::db::DatabaseErrorKind;
+
+        let policy = RetryPolicy::new(2).with_base_delay(Duration::ZERO);
+        let deadlock = ErrorKind::Database(DatabaseErrorKind::Deadlock);
+        assert_eq!(policy.max_attempts_for(&deadlock), 4);
+        assert_eq!(
+            policy.max_attempts_for(&ErrorKind::Database(DatabaseErrorKind::ConnectionLost)),
+            2
+        );
+        assert_eq!(policy.max_attempts_for(&ErrorKind::Timeout), 2);
+
+        let mut calls = 0;
+        let err = retry_with(&policy, || -> Result<(), Error> {
+            calls += 1;
+            Err(Error::temporary(deadlock.clone(), "deadlock"))
+        })
+        .unwrap_err();
+        assert_eq!(calls, 4);
+        assert_eq!(err.attempt(), Some(4));
+    }
+
+    #[test]
+    fn test_retry_if_all_retryable_stops_on_permanent_frame() {
+        let policy = RetryPolicy::new(5).with_base_delay(Duration::ZERO);
+        let failing = || -> crate::Result<()> {
+            Err(Error::permanent(ErrorKind::NotFound, "gone")
+                .raise()
+                .raise(Error::temporary(ErrorKind::Timeout, "outer")))
+        };
+
+        let mut calls = 0;
+        let result = failing().retry_if_all_retryable(&policy, || {
+            calls += 1;
+            failing()
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 0);
+    }
+
+    #[test]
+    fn test_retry_if_all_retryable_retries_temporary_tree() {
+        let policy = RetryPolicy::new(5).with_base_delay(Duration::ZERO);
+        let failing = || -> crate::Result<u32> {
+            Err(Error::temporary(ErrorKind::Timeout, "inner")
+                .raise()
+                .raise(Error::temporary(ErrorKind::Timeout, "outer")))
+        };
+
+        let mut calls = 0;
+        let result = failing().retry_if_all_retryable(&policy, || {
+            calls += 1;
+            failing()
+        });
+        assert!(result.is_err());
+        assert_eq!(calls, 4);
+
+        let mut calls = 0;
+        let value = failing().retry_if_all_retryable(&policy, || {
+            calls += 1;
+            if calls == 2 { Ok(7) } else { failing() }
+        });
+        assert_eq!(value.unwrap(), 7);
+        assert_eq!(calls, 2);
+    }
+
+    #[test]
+    fn test_retry_with_succeeds() {
+        let policy = RetryPolicy::new(3).with_base_delay(Duration::ZERO);
+        let mut calls = 0;
+        let value = retry_with(&policy, || {
+            calls += 1;
+            if calls < 3 {
+                Err(Error::timeout())
+            } else {
+                Ok(calls)
+            }
+        });
+        assert_eq!(value, Ok(3));
+    }
 }
@@ -1,5 +1,8 @@
 use std::fmt;
 
+use crate::kind::{ErrorKind, Severity};
+use crate::status::ErrorStatus;
+
 /// Storage-specific error kinds.
 ///
 /// These errors categorize storage-related failures by what the caller should do.
@@ -67,6 +70,32 @@ pub enum StorageErrorKind {
 }
 
 impl StorageErrorKind {
+    /// Returns every variant.
+    #[inline]
+    pub fn all() -> &'static [StorageErrorKind] {
+        &[
+            StorageErrorKind::NotFound,
+            StorageErrorKind::DirectoryNotFound,
+            StorageErrorKind::PermissionDenied,
+            StorageErrorKind::AlreadyExists,
+            StorageErrorKind::IsDirectory,
+            StorageErrorKind::NotDirectory,
+            StorageErrorKind::DiskFull,
+            StorageErrorKind::IoError,
+            StorageErrorKind::FileNameTooLong,
+            StorageErrorKind::PathTooLong,
+            StorageErrorKind::TooManyOpenFiles,
+            StorageErrorKind::ReadOnly,
+            StorageErrorKind::StorageFull,
+            StorageErrorKind::NetworkError,
+            StorageErrorKind::NetworkTimeout,
+            StorageErrorKind::InvalidFilename,
+            StorageErrorKind::InvalidPath,
+            StorageErrorKind::SymlinkLoop,
+            StorageErrorKind::TooManySymlinks,
+        ]
+    }
+
     /// Returns `true` if this storage error kind represents a retryable condition.
     #[inline]
     pub fn is_retryable(&self) -> bool {
@@ -93,6 +122,13 @@ impl StorageErrorKind {
         }
     }
 
+    /// Returns the status matching [`is_retryable`](StorageErrorKind::is_retryable):
+    /// `Temporary` for retryable kinds, `Permanent` otherwise.
+    #[inline]
+    pub fn default_status(&self) -> ErrorStatus {
+        ErrorStatus::from_retryable(self.is_retryable())
+    }
+
     /// Returns `true` if this is a path-related error.
     #[inline]
     pub fn is_path_error(&self) -> bool {
@@ -138,6 +174,32 @@ impl StorageErrorKind {
         )
     }
 
+    /// Returns the core [`ErrorKind`] with the same meaning, if there is one.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::ErrorKind;
+    /// use erract::storage::StorageErrorKind;
+    ///
+    /// assert_eq!(StorageErrorKind::DirectoryNotFound.to_core_kind(), Some(ErrorKind::NotFound));
+    /// assert_eq!(StorageErrorKind::SymlinkLoop.to_core_kind(), None);
+    /// ```
+    #[inline]
+    pub fn to_core_kind(&self) -> Option<ErrorKind> {
+        match self {
+            StorageErrorKind::NotFound | StorageErrorKind::DirectoryNotFound => {
+                Some(ErrorKind::NotFound)
+            }
+            StorageErrorKind::PermissionDenied => Some(ErrorKind::PermissionDenied),
+            StorageErrorKind::NetworkTimeout => Some(ErrorKind::Timeout),
+            StorageErrorKind::InvalidFilename | StorageErrorKind::InvalidPath => {
+                Some(ErrorKind::Validation)
+            }
+            _ => None,
+        }
+    }
+
     /// Returns `true` if this is an I/O error.
     #[inline]
     pub fn is_io_error(&self) -> bool {
@@ -173,6 +235,55 @@ impl StorageErrorKind {
         )
     }
 
+    /// Returns the default severity of this error kind.
+    #[inline]
+    pub fn severity(&self) -> Severity {
+        match self {
+            StorageErrorKind::NotFound => Severity::Warning,
+            StorageErrorKind::DirectoryNotFound => Severity::Warning,
+            StorageErrorKind::AlreadyExists => Severity::Warning,
+            StorageErrorKind::IsDirectory => Severity::Warning,
+            StorageErrorKind::NotDirectory => Severity::Warning,
+            StorageErrorKind::FileNameTooLong => Severity::Warning,
+            StorageErrorKind::PathTooLong => Severity::Warning,
+            StorageErrorKind::InvalidFilename => Severity::Warning,
+            StorageErrorKind::InvalidPath => Severity::Warning,
+            StorageErrorKind::NetworkError => Severity::Warning,
+            StorageErrorKind::NetworkTimeout => Severity::Warning,
+            StorageErrorKind::PermissionDenied => Severity::Error,
+            StorageErrorKind::IoError => Severity::Error,
+            StorageErrorKind::TooManyOpenFiles => Severity::Error,
+            StorageErrorKind::ReadOnly => Severity::Error,
+            StorageErrorKind::SymlinkLoop => Severity::Error,
+            StorageErrorKind::TooManySymlinks => Severity::Error,
+            StorageErrorKind::DiskFull => Severity::Critical,
+            StorageErrorKind::StorageFull => Severity::Critical,
+        }
+    }
+
+    /// Returns the HTTP status code to respond with for this error kind.
+    #[inline]
+    pub fn http_status(&self) -> u16 {
+        match self {
+            StorageErrorKind::NotFound | StorageErrorKind::DirectoryNotFound => 404,
+            StorageErrorKind::PermissionDenied => 403,
+            StorageErrorKind::AlreadyExists => 409,
+            StorageErrorKind::DiskFull | StorageErrorKind::StorageFull => 507,
+            StorageErrorKind::NetworkTimeout => 504,
+            StorageErrorKind::IoError
+            | StorageErrorKind::TooManyOpenFiles
+            | StorageErrorKind::ReadOnly
+            | StorageErrorKind::NetworkError => 503,
+            StorageErrorKind::IsDirectory
+            | StorageErrorKind::NotDirectory
+            | StorageErrorKind::FileNameTooLong
+            | StorageErrorKind::PathTooLong
+            | StorageErrorKind::InvalidFilename
+            | StorageErrorKind::InvalidPath => 400,
+            StorageErrorKind::SymlinkLoop | StorageErrorKind::TooManySymlinks => 500,
+        }
+    }
+
     /// Returns a machine-readable string representation of this storage error kind.
     #[inline]
     pub fn to_machine_string(&self) -> &'static str {
@@ -198,6 +309,70 @@ impl StorageErrorKind {
             StorageErrorKind::TooManySymlinks => "too_many_symlinks",
         }
     }
+
+    /// Classifies a raw `errno` value from a failed syscall.
+    ///
+    /// Returns `None` for errno values without a storage-specific meaning.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use erract::storage::StorageErrorKind;
+    ///
+    /// assert_eq!(
+    ///     StorageErrorKind::from_errno(libc::ENOENT),
+    ///     Some(StorageErrorKind::NotFound)
+    /// );
+    /// ```
+    #[cfg(unix)]
+    pub fn from_errno(errno: i32) -> Option<Self> {
+        let kind = match errno {
+            libc::ENOENT => StorageErrorKind::NotFound,
+            libc::EACCES | libc::EPERM => StorageErrorKind::PermissionDenied,
+            libc::EEXIST => StorageErrorKind::AlreadyExists,
+            libc::EISDIR => StorageErrorKind::IsDirectory,
+            libc::ENOTDIR => StorageErrorKind::NotDirectory,
+            libc::ENOSPC | libc::EDQUOT => StorageErrorKind::DiskFull,
+            libc::EIO => StorageErrorKind::IoError,
+            libc::ENAMETOOLONG => StorageErrorKind::FileNameTooLong,
+            libc::EMFILE | libc::ENFILE => StorageErrorKind::TooManyOpenFiles,
+            libc::EROFS => StorageErrorKind::ReadOnly,
+            libc::ELOOP => StorageErrorKind::SymlinkLoop,
+            libc::ETIMEDOUT => StorageErrorKind::NetworkTimeout,
+            libc::ENETDOWN | libc::ENETUNREACH | libc::ECONNRESET | libc::ESTALE => {
+                StorageErrorKind::NetworkError
+            }
+            _ => return None,
+        };
+        Some(kind)
+    }
+
+    /// Parses the output of [`StorageErrorKind::to_machine_string`] back into a kind.
+    pub fn from_machine_string(s: &str) -> Option<Self> {
+        let kind = match s {
+            "not_found" => StorageErrorKind::NotFound,
+            "directory_not_found" => StorageErrorKind::DirectoryNotFound,
+            "permission_denied" => StorageErrorKind::PermissionDenied,
+            "already_exists" => StorageErrorKind::AlreadyExists,
+            "is_directory" => StorageErrorKind::IsDirectory,
+            "not_directory" => StorageErrorKind::NotDirectory,
+            "disk_full" => StorageErrorKind::DiskFull,
+            "io_error" => StorageErrorKind::IoError,
+            "file_name_too_long" => StorageErrorKind::FileNameTooLong,
+            "path_too_long" => StorageErrorKind::PathTooLong,
+            "too_many_open_files" => StorageErrorKind::TooManyOpenFiles,
+            "read_only" => StorageErrorKind::ReadOnly,
+            "storage_full" => StorageErrorKind::StorageFull,
+            "network_error" => StorageErrorKind::NetworkError,
+            "network_timeout" => StorageErrorKind::NetworkTimeout,
+            "invalid_filename" => StorageErrorKind::InvalidFilename,
+            "invalid_path" => StorageErrorKind::InvalidPath,
+            "symlink_loop" => StorageErrorKind::SymlinkLoop,
+            "too_many_symlinks" => StorageErrorKind::TooManySymlinks,
+            _ => return None,
+        };
+        Some(kind)
+    }
 }
 
 impl fmt::Display for StorageErrorKind {
@@ -229,6 +404,26 @@ impl fmt::Display for StorageErrorKind {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::test_util::{
+        assert_default_status_matches_retryability, assert_lists_every_variant,
+    };
+
+    #[test]
+    fn test_default_status_matches_retryability() {
+        assert_default_status_matches_retryability(
+            StorageErrorKind::all(),
+            StorageErrorKind::default_status,
+            StorageErrorKind::is_retryable,
+        );
+        assert_eq!(
+            StorageErrorKind::NetworkTimeout.default_status(),
+            ErrorStatus::Temporary
+        );
+        assert_eq!(
+            StorageErrorKind::NotFound.default_status(),
+            ErrorStatus::Permanent
+        );
+    }
 
     #[test]
     fn test_not_found_not_retryable() {
@@ -250,6 +445,85 @@ mod tests {
         assert!(!StorageErrorKind::PermissionDenied.is_retryable());
     }
 
+    #[cfg(unix)]
+    #[test]
+    fn test_from_errno() {
+        let cases = [
+            (libc::ENOENT, StorageErrorKind::NotFound),
+            (libc::EACCES, StorageErrorKind::PermissionDenied),
+            (libc::EPERM, StorageErrorKind::PermissionDenied),
+            (libc::EEXIST, StorageErrorKind::AlreadyExists),
+            (libc::ENOSPC, StorageErrorKind::DiskFull),
+            (libc::EDQUOT, StorageErrorKind::DiskFull),
+            (libc::EMFILE, StorageErrorKind::TooManyOpenFiles),
+            (libc::EROFS, StorageErrorKind::ReadOnly),
+            (libc::ENAMETOOLONG, StorageErrorKind::FileNameTooLong),
+            (libc::ELOOP, StorageErrorKind::SymlinkLoop),
+        ];
+        for (errno, kind) in cases {
+            assert_eq!(StorageErrorKind::from_errno(errno), Some(kind), "{errno}");
+        }
+        assert_eq!(StorageErrorKind::from_errno(libc::EINVAL), None);
+    }
+
+    #[test]
+    fn test_http_status() {
+        assert_eq!(StorageErrorKind::NotFound.http_status(), 404);
+        assert_eq!(StorageErrorKind::DiskFull.http_status(), 507);
+        assert_eq!(
+            crate::ErrorKind::Storage(StorageErrorKind::NotFound).http_status(),
+            404
+        );
+    }
+
+    #[test]
+    fn test_all_covers_every_variant() {
+        assert_lists_every_variant(StorageErrorKind::all(), 19, |kind| match kind {
+            StorageErrorKind::NotFound => 0,
+            StorageErrorKind::DirectoryNotFound => 1,
+            StorageErrorKind::PermissionDenied => 2,
+            StorageErrorKind::AlreadyExists => 3,
+            StorageErrorKind::IsDirectory => 4,
+            StorageErrorKind::NotDirectory => 5,
+            StorageErrorKind::DiskFull => 6,
+            StorageErrorKind::IoError => 7,
+            StorageErrorKind::FileNameTooLong => 8,
+            StorageErrorKind::PathTooLong => 9,
+            StorageErrorKind::TooManyOpenFiles => 10,
+            StorageErrorKind::ReadOnly => 11,
+            StorageErrorKind::StorageFull => 12,
+            StorageErrorKind::NetworkError => 13,
+            StorageErrorKind::NetworkTimeout => 14,
+            StorageErrorKind::InvalidFilename => 15,
+            StorageErrorKind::InvalidPath => 16,
+            StorageErrorKind::SymlinkLoop => 17,
+            StorageErrorKind::TooManySymlinks => 18,
+        });
+    }
+
+    #[test]
+    fn test_to_core_kind() {
+        let cases = [
+            (StorageErrorKind::NotFound, Some(ErrorKind::NotFound)),
+            (
+                StorageErrorKind::DirectoryNotFound,
+                Some(ErrorKind::NotFound),
+            ),
+            (
+                StorageErrorKind::PermissionDenied,
+                Some(ErrorKind::PermissionDenied),
+            ),
+            (StorageErrorKind::NetworkTimeout, Some(ErrorKind::Timeout)),
+            (StorageErrorKind::InvalidPath, Some(ErrorKind::Validation)),
+            (StorageErrorKind::SymlinkLoop, None),
+            (StorageErrorKind::DiskFull, None),
+            (StorageErrorKind::IoError, None),
+        ];
+        for (kind, core) in cases {
+            assert_eq!(kind.to_core_kind(), core, "{kind:?}");
+        }
+    }
+
     #[test]
     fn test_display() {
         assert_eq!(StorageErrorKind::NotFound.to_string(), "not found");
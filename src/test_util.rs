@@ -0,0 +1,42 @@
+//! Assertions shared by the unit tests of the error kind modules.
+
+/// Asserts that `all` lists each of `count` variants exactly once.
+///
+/// `index` should be an exhaustive `match` numbering the variants from 0, so a
+/// new variant fails to compile until it is numbered, and then fails here until
+/// `all` lists it.
+pub(crate) fn assert_lists_every_variant<T: std::fmt::Debug>(
+    all: &[T],
+    count: usize,
+    index: impl Fn(&T) -> usize,
+) {
+    let mut seen = vec![false; count];
+    for variant in all {
+        let i = index(variant);
+        assert!(
+            i < count,
+            "{variant:?} is numbered {i}, expected below {count}"
+        );
+        assert!(!seen[i], "{variant:?} is listed twice");
+        seen[i] = true;
+    }
+    let missing: Vec<usize> = (0..count).filter(|&i| !seen[i]).collect();
+    assert!(missing.is_empty(), "variants {missing:?} are not listed");
+}
+
+/// Asserts that every kind in `all` defaults to a status whose retryability
+/// matches the kind's own.
+#[cfg(any(feature = "http", feature = "db", feature = "storage"))]
+pub(crate) fn assert_default_status_matches_retryability<T: std::fmt::Debug>(
+    all: &[T],
+    default_status: impl Fn(&T) -> crate::ErrorStatus,
+    is_retryable: impl Fn(&T) -> bool,
+) {
+    for kind in all {
+        assert_eq!(
+            default_status(kind).is_retryable(),
+            is_retryable(kind),
+            "{kind:?}"
+        );
+    }
+}
@@ -0,0 +1,606 @@
+use std::fmt::Write;
+
+use crate::status::RetryDecision;
+use crate::{Error, ErrorKind, ErrorStatus, TraceContext};
+
+/// Receives the fields of an [`Error`] in a fixed order, for custom output formats.
+///
+/// [`Error::visit`] calls `kind`, `status`, `message` and `operation` once each,
+/// then the optional fields that are set, then `context_pair` for each context
+/// entry in insertion order. The optional callbacks default to doing nothing.
+///
+/// # Examples
+///
+/// ```
+/// use erract::{Error, ErrorKind, ErrorStatus, ErrorVisitor};
+///
+/// #[derive(Default)]
+/// struct Keys(Vec<String>);
+///
+/// impl ErrorVisitor for Keys {
+///     fn kind(&mut self, _: &ErrorKind) {}
+///     fn status(&mut self, _: &ErrorStatus) {}
+///     fn message(&mut self, _: &str) {}
+///     fn operation(&mut self, _: Option<&str>) {}
+///     fn context_pair(&mut self, key: &str, _: &str) {
+///         self.0.push(key.to_owned());
+///     }
+/// }
+///
+/// let error = Error::not_found().with_context("user_id", "42");
+/// let mut keys = Keys::default();
+/// error.visit(&mut keys);
+/// assert_eq!(keys.0, ["user_id"]);
+/// ```
+pub trait ErrorVisitor {
+    /// Visits the error kind.
+    fn kind(&mut self, kind: &ErrorKind);
+
+    /// Visits the retry status.
+    fn status(&mut self, status: &ErrorStatus);
+
+    /// Visits the error message.
+    fn message(&mut self, message: &str);
+
+    /// Visits the operation, if any.
+    fn operation(&mut self, operation: Option<&str>);
+
+    /// Visits the localization message ID, when set.
+    fn message_id(&mut self, _id: &str) {}
+
+    /// Visits the operation the error originated in, when known.
+    fn origin(&mut self, _origin: &str) {}
+
+    /// Visits the application-specific code, when set.
+    fn code(&mut self, _code: &str) {}
+
+    /// Visits the attempt number, when set.
+    fn attempt(&mut self, _attempt: u32) {}
+
+    /// Called when the error is marked fatal.
+    fn fatal(&mut self) {}
+
+    /// Visits the trace context, when set.
+    fn trace(&mut self, _trace: &TraceContext) {}
+
+    /// Visits the retry decision, when recorded.
+    fn retry_decision(&mut self, _decision: &RetryDecision) {}
+
+    /// Visits one context entry.
+    fn context_pair(&mut self, key: &str, value: &str);
+}
+
+impl Error {
+    /// Feeds the fields of this error to `visitor`.
+    ///
+    /// See [`ErrorVisitor`] for the order of callbacks.
+    pub fn visit<V: ErrorVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.visit_fields(visitor);
+        for (k, v) in &self.context() {
+            visitor.context_pair(k, v);
+        }
+    }
+
+    /// Like [`visit`](Error::visit), but with context pairs sorted by key.
+    ///
+    /// The sort is stable, so values of a repeated key keep their insertion order.
+    pub(crate) fn visit_sorted<V: ErrorVisitor + ?Sized>(&self, visitor: &mut V) {
+        self.visit_fields(visitor);
+        let mut context = self.context();
+        context.sort_by(|(a, _), (b, _)| a.cmp(b));
+        for (k, v) in &context {
+            visitor.context_pair(k, v);
+        }
+    }
+
+    fn visit_fields<V: ErrorVisitor + ?Sized>(&self, visitor: &mut V) {
+        visitor.kind(self.kind());
+        visitor.status(self.status());
+        visitor.message(self.message());
+        visitor.operation(self.operation());
+        if let Some(id) = self.message_id() {
+            visitor.message_id(id);
+        }
+        if let Some(origin) = self.recorded_origin() {
+            visitor.origin(origin);
+        }
+        if let Some(code) = self.code() {
+            visitor.code(code);
+        }
+        if let Some(attempt) = self.attempt() {
+            visitor.attempt(attempt);
+        }
+        if self.is_fatal() {
+            visitor.fatal();
+        }
+        if let Some(trace) = self.trace_context() {
+            visitor.trace(trace);
+        }
+        if let Some(decision) = self.retry_decision() {
+            visitor.retry_decision(decision);
+        }
+    }
+}
+
+/// Writes the `kind=...;status=...` form used by [`Error::to_machine_string`].
+pub(crate) struct MachineVisitor<'a> {
+    buf: &'a mut String,
+    in_context: bool,
+}
+
+impl<'a> MachineVisitor<'a> {
+    pub(crate) fn new(buf: &'a mut String) -> Self {
+        Self {
+            buf,
+            in_context: false,
+        }
+    }
+
+    pub(crate) fn finish(self) {
+        if self.in_context {
+            self.buf.push(']');
+        }
+    }
+}
+
+impl ErrorVisitor for MachineVisitor<'_> {
+    fn kind(&mut self, kind: &ErrorKind) {
+        self.buf.push_str("kind=");
+        let _ = kind.write_machine_string(self.buf);
+    }
+
+    fn status(&mut self, status: &ErrorStatus) {
+        self.buf.push_str(";status=");
+        self.buf.push_str(status.to_machine_string());
+    }
+
+    fn message(&mut self, message: &str) {
+        self.buf.push_str(";message=");
+        self.buf.push_str(message);
+    }
+
+    fn operation(&mut self, operation: Option<&str>) {
+        if let Some(op) = operation {
+            self.buf.push_str(";operation=");
+            self.buf.push_str(op);
+        }
+    }
+
+    fn code(&mut self, code: &str) {
+        self.buf.push_str(";code=");
+        self.buf.push_str(code);
+    }
+
+    fn context_pair(&mut self, key: &str, value: &str) {
+        if self.in_context {
+            self.buf.push(',');
+        } else {
+            self.buf.push_str(";context=[");
+            self.in_context = true;
+        }
+        self.buf.push_str(key);
+        self.buf.push('=');
+        self.buf.push_str(value);
+    }
+}
+
+/// Text output the JSON writers can target: a `String` or a byte buffer.
+pub(crate) trait JsonBuf: Write {
+    #[inline]
+    fn push(&mut self, c: char) {
+        let _ = self.write_char(c);
+    }
+
+    #[inline]
+    fn push_str(&mut self, s: &str) {
+        let _ = self.write_str(s);
+    }
+}
+
+impl JsonBuf for String {}
+
+/// Adapts a `Vec<u8>` so UTF-8 text is written into it directly.
+pub(crate) struct ByteBuf<'a>(pub(crate) &'a mut Vec<u8>);
+
+impl Write for ByteBuf<'_> {
+    #[inline]
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.0.extend_from_slice(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl JsonBuf for ByteBuf<'_> {}
+
+/// Writes JSON objects, compact or indented, for [`Error::write_json`],
+/// [`Error::write_json_pretty`] and [`Error::write_json_bytes`].
+pub(crate) struct JsonVisitor<'a, W: JsonBuf> {
+    buf: &'a mut W,
+    indent: Option<usize>,
+    inline_json: bool,
+    flat: bool,
+    first: bool,
+    in_context: bool,
+}
+
+/// Top-level names the JSON writers may emit; flat output prefixes context keys
+/// that collide with them.
+const RESERVED_FIELDS: &[&str] = &[
+    "kind",
+    "status",
+    "message",
+    "operation",
+    "message_id",
+    "origin",
+    "code",
+    "attempt",
+    "fatal",
+    "trace_id",
+    "span_id",
+    "retry_decision",
+];
+
+impl<'a, W: JsonBuf> JsonVisitor<'a, W> {
+    pub(crate) fn new(buf: &'a mut W, indent: Option<usize>) -> Self {
+        buf.push('{');
+        Self {
+            buf,
+            indent,
+            inline_json: false,
+            flat: false,
+            first: true,
+            in_context: false,
+        }
+    }
+
+    /// Writes context values holding a JSON object or array without quoting them.
+    #[cfg(feature = "serde")]
+    pub(crate) fn inline_json(mut self) -> Self {
+        self.inline_json = true;
+        self
+    }
+
+    /// Writes context pairs as top-level members instead of a nested `"context"` object.
+    ///
+    /// Keys that collide with a reserved field name are written as `context.<key>`.
+    pub(crate) fn flat(mut self) -> Self {
+        self.flat = true;
+        self
+    }
+
+    pub(crate) fn finish(self) {
+        if self.in_context {
+            if let Some(width) = self.indent {
+                self.buf.push('\n');
+                push_indent(self.buf, width);
+            }
+            self.buf.push('}');
+        }
+        if self.indent.is_some() {
+            self.buf.push('\n');
+        }
+        self.buf.push('}');
+    }
+
+    fn field(&mut self, name: &str) {
+        self.separator();
+        self.buf.push('"');
+        self.buf.push_str(name);
+        self.buf.push_str(r#"":"#);
+        if self.indent.is_some() {
+            self.buf.push(' ');
+        }
+    }
+
+    fn separator(&mut self) {
+        if !self.first {
+            self.buf.push(',');
+        }
+        self.first = false;
+        if let Some(width) = self.indent {
+            self.buf.push('\n');
+            push_indent(self.buf, width);
+        }
+    }
+
+    fn context_value(&mut self, value: &str) {
+        if self.inline_json && is_json_container(value) {
+            self.buf.push_str(value);
+            return;
+        }
+        self.buf.push('"');
+        write_escaped(self.buf, value);
+        self.buf.push('"');
+    }
+
+    fn string(&mut self, name: &str, value: &str) {
+        self.field(name);
+        self.buf.push('"');
+        write_escaped(self.buf, value);
+        self.buf.push('"');
+    }
+}
+
+impl<W: JsonBuf> ErrorVisitor for JsonVisitor<'_, W> {
+    fn kind(&mut self, kind: &ErrorKind) {
+        // Machine strings are plain ASCII identifiers and need no escaping.
+        self.field("kind");
+        self.buf.push('"');
+        let _ = kind.write_machine_string(self.buf);
+        self.buf.push('"');
+    }
+
+    fn status(&mut self, status: &ErrorStatus) {
+        self.string("status", status.to_machine_string());
+    }
+
+    fn message(&mut self, message: &str) {
+        self.string("message", message);
+    }
+
+    fn operation(&mut self, operation: Option<&str>) {
+        if let Some(op) = operation {
+            self.string("operation", op);
+        }
+    }
+
+    fn message_id(&mut self, id: &str) {
+        self.string("message_id", id);
+    }
+
+    fn origin(&mut self, origin: &str) {
+        self.string("origin", origin);
+    }
+
+    fn code(&mut self, code: &str) {
+        self.string("code", code);
+    }
+
+    fn attempt(&mut self, attempt: u32) {
+        self.field("attempt");
+        let _ = write!(self.buf, "{attempt}");
+    }
+
+    fn fatal(&mut self) {
+        self.field("fatal");
+        self.buf.push_str("true");
+    }
+
+    fn trace(&mut self, trace: &TraceContext) {
+        self.field("trace_id");
+        self.buf.push('"');
+        write_hex(self.buf, &trace.trace_id);
+        self.buf.push('"');
+        self.field("span_id");
+        self.buf.push('"');
+        write_hex(self.buf, &trace.span_id);
+        self.buf.push('"');
+    }
+
+    fn retry_decision(&mut self, decision: &RetryDecision) {
+        self.field("retry_decision");
+        self.buf.push_str(r#"{"outcome":""#);
+        self.buf.push_str(decision.to_machine_string());
+        self.buf.push('"');
+        match decision {
+            RetryDecision::Retried { attempts } => {
+                let _ = write!(self.buf, r#","attempts":{attempts}"#);
+            }
+            RetryDecision::GaveUp { reason } => {
+                self.buf.push_str(r#","reason":""#);
+                write_escaped(self.buf, reason);
+                self.buf.push('"');
+            }
+            RetryDecision::NotRetryable => {}
+        }
+        self.buf.push('}');
+    }
+
+    fn context_pair(&mut self, key: &str, value: &str) {
+        if self.flat {
+            self.separator();
+            self.buf.push('"');
+            if RESERVED_FIELDS.contains(&key) {
+                self.buf.push_str("context.");
+            }
+            write_escaped(self.buf, key);
+            self.buf.push_str(r#"":"#);
+            if self.indent.is_some() {
+                self.buf.push(' ');
+            }
+            self.context_value(value);
+            return;
+        }
+        if self.in_context {
+            self.buf.push(',');
+        } else {
+            self.field("context");
+            self.buf.push('{');
+            self.in_context = true;
+        }
+        if let Some(width) = self.indent {
+            self.buf.push('\n');
+            push_indent(self.buf, width * 2);
+        }
+        self.buf.push('"');
+        write_escaped(self.buf, key);
+        self.buf.push_str(r#"":"#);
+        if self.indent.is_some() {
+            self.buf.push(' ');
+        }
+        self.context_value(value);
+    }
+}
+
+#[cfg(feature = "serde")]
+fn is_json_container(value: &str) -> bool {
+    (value.starts_with('{') || value.starts_with('['))
+        && serde_json::from_str::<serde::de::IgnoredAny>(value).is_ok()
+}
+
+#[cfg(not(feature = "serde"))]
+fn is_json_container(_value: &str) -> bool {
+    false
+}
+
+#[inline]
+fn write_hex(buf: &mut impl JsonBuf, bytes: &[u8]) {
+    for byte in bytes {
+        let _ = write!(buf, "{byte:02x}");
+    }
+}
+
+#[inline]
+fn push_indent(buf: &mut impl JsonBuf, width: usize) {
+    for _ in 0..width {
+        buf.push(' ');
+    }
+}
+
+#[inline]
+pub(crate) fn write_escaped(buf: &mut impl JsonBuf, s: &str) {
+    // Clean runs are copied in bulk; only bytes that may need escaping fall
+    // through to the per-char path. `0xC2` leads the C1 control range.
+    let bytes = s.as_bytes();
+    let mut start = 0;
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        if b >= 0x20 && b != b'"' && b != b'\\' && b != 0x7f && b != 0xc2 {
+            i += 1;
+            continue;
+        }
+        buf.push_str(&s[start..i]);
+        let c = s[i..].chars().next().unwrap_or_default();
+        write_escaped_char(buf, c);
+        i += c.len_utf8();
+        start = i;
+    }
+    buf.push_str(&s[start..]);
+}
+
+fn write_escaped_char(buf: &mut impl JsonBuf, c: char) {
+    match c {
+        '"' => buf.push_str(r#"\""#),
+        '\\' => buf.push_str(r#"\\"#),
+        '\n' => buf.push_str(r#"\n"#),
+        '\r' => buf.push_str(r#"\r"#),
+        '\t' => buf.push_str(r#"\t"#),
+        c if c.is_control() => {
+            let _ = write!(buf, r#"\u{:04x}"#, c as u32);
+        }
+        c => buf.push(c),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Debug, PartialEq)]
+    enum Call {
+        Kind(ErrorKind),
+        Status(ErrorStatus),
+        Message(String),
+        Operation(Option<String>),
+        Code(String),
+        Attempt(u32),
+        Fatal,
+        Pair(String, String),
+    }
+
+    #[derive(Default)]
+    struct Recorder(Vec<Call>);
+
+    impl ErrorVisitor for Recorder {
+        fn kind(&mut self, kind: &ErrorKind) {
+            self.0.push(Call::Kind(kind.clone()));
+        }
+        fn status(&mut self, status: &ErrorStatus) {
+            self.0.push(Call::Status(status.clone()));
+        }
+        fn message(&mut self, message: &str) {
+            self.0.push(Call::Message(message.to_owned()));
+        }
+        fn operation(&mut self, operation: Option<&str>) {
+            self.0.push(Call::Operation(operation.map(str::to_owned)));
+        }
+        fn code(&mut self, code: &str) {
+            self.0.push(Call::Code(code.to_owned()));
+        }
+        fn attempt(&mut self, attempt: u32) {
+            self.0.push(Call::Attempt(attempt));
+        }
+        fn fatal(&mut self) {
+            self.0.push(Call::Fatal);
+        }
+        fn context_pair(&mut self, key: &str, value: &str) {
+            self.0.push(Call::Pair(key.to_owned(), value.to_owned()));
+        }
+    }
+
+    #[test]
+    fn test_visit_sequence() {
+        let error = Error::temporary(ErrorKind::Timeout, "slow")
+            .with_operation("fetch")
+            .with_code("E1001")
+            .with_attempt(2)
+            .mark_fatal()
+            .with_context("a", "1")
+            .with_context("b", "2");
+
+        let mut recorder = Recorder::default();
+        error.visit(&mut recorder);
+        assert_eq!(
+            recorder.0,
+            [
+                Call::Kind(ErrorKind::Timeout),
+                Call::Status(ErrorStatus::Temporary),
+                Call::Message("slow".into()),
+                Call::Operation(Some("fetch".into())),
+                Call::Code("E1001".into()),
+                Call::Attempt(2),
+                Call::Fatal,
+                Call::Pair("a".into(), "1".into()),
+                Call::Pair("b".into(), "2".into()),
+            ]
+        );
+    }
+
+    fn write_escaped_slow(buf: &mut String, s: &str) {
+        for c in s.chars() {
+            write_escaped_char(buf, c);
+        }
+    }
+
+    #[test]
+    fn test_write_escaped_matches_per_char() {
+        let inputs = [
+            "",
+            "plain ascii",
+            "quote \" and backslash \\",
+            "line\nbreak\r\ttab",
+            "\u{0}\u{1f}\u{7f}",
+            "c1 \u{80}\u{9f} then é¡ü",
+            "\u{a0}\u{bf}¬",
+            "emoji 🦀 \"mixed\"\n",
+            "\"",
+            "trailing\\",
+        ];
+        for input in inputs {
+            let mut fast = String::new();
+            write_escaped(&mut fast, input);
+            let mut slow = String::new();
+            write_escaped_slow(&mut slow, input);
+            assert_eq!(fast, slow, "{input:?}");
+        }
+    }
+
+    #[test]
+    fn test_visit_minimal() {
+        let mut recorder = Recorder::default();
+        Error::not_found().visit(&mut recorder);
+        assert_eq!(recorder.0.len(), 4);
+        assert_eq!(recorder.0[3], Call::Operation(None));
+    }
+}
@@ -0,0 +1,20 @@
+//! The key sanitizer is process-wide, so it is exercised in its own test binary.
+
+use erract::{Error, set_context_key_sanitizer};
+
+#[test]
+fn test_custom_sanitizer() {
+    set_context_key_sanitizer(|key| {
+        (!key.contains('\n')).then(|| key.replace(|c: char| c.is_control(), "_"))
+    });
+
+    let error = Error::not_found()
+        .with_context("injected\nlevel=error", "x")
+        .with_context("tab\tkey", "1")
+        .with_context("plain", "2");
+    let context = error.context();
+    assert_eq!(context.len(), 2);
+    assert_eq!(context[0].0, "tab_key");
+    assert_eq!(context[1].0, "plain");
+    assert!(!error.to_json().contains("injected"));
+}
@@ -0,0 +1,60 @@
+//! The derive macro expands to `::erract` paths, so it is exercised from outside the crate.
+#![cfg(feature = "derive")]
+
+use std::fmt;
+
+use erract::{Error, ErrorContext};
+
+#[derive(Debug)]
+enum Method {
+    Get,
+}
+
+#[derive(ErrorContext)]
+struct RequestInfo<'a> {
+    request_id: &'a str,
+    attempt: u32,
+    #[error_context(debug)]
+    method: Method,
+    #[error_context(skip)]
+    token: String,
+}
+
+struct Region;
+
+impl fmt::Display for Region {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("eu-west")
+    }
+}
+
+#[derive(ErrorContext)]
+struct Placement<T: fmt::Display> {
+    region: T,
+}
+
+#[test]
+fn test_derive_error_context_keys() {
+    let info = RequestInfo {
+        request_id: "req-7",
+        attempt: 2,
+        method: Method::Get,
+        token: "secret".into(),
+    };
+    let pairs = info.error_context();
+    let keys: Vec<_> = pairs.iter().map(|(k, _)| k.as_ref()).collect();
+    assert_eq!(keys, ["request_id", "attempt", "method"]);
+    assert!(!pairs.iter().any(|(_, v)| *v == info.token));
+
+    let error = Error::not_found().with_context_from(&info);
+    assert_eq!(
+        error.to_string(),
+        "not found [request_id: req-7, attempt: 2, method: Get]"
+    );
+}
+
+#[test]
+fn test_derive_error_context_generics() {
+    let error = Error::timeout().with_context_from(&Placement { region: Region });
+    assert_eq!(error.context()[0], ("region".into(), "eu-west".into()));
+}
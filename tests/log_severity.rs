@@ -0,0 +1,44 @@
+//! The log threshold and logger are process-wide, so they are exercised in their own test binary.
+#![cfg(feature = "log")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use erract::error::min_log_severity;
+use erract::{Error, ErrorKind, Severity, set_min_log_severity};
+
+static RECORDS: AtomicUsize = AtomicUsize::new(0);
+
+struct CountingLogger;
+
+impl log::Log for CountingLogger {
+    fn enabled(&self, _: &log::Metadata<'_>) -> bool {
+        true
+    }
+
+    fn log(&self, _: &log::Record<'_>) {
+        RECORDS.fetch_add(1, Ordering::SeqCst);
+    }
+
+    fn flush(&self) {}
+}
+
+#[test]
+fn test_emit_respects_min_severity() {
+    log::set_logger(&CountingLogger).unwrap();
+    log::set_max_level(log::LevelFilter::Trace);
+
+    let warning = Error::permanent(ErrorKind::NotFound, "missing");
+    let error = Error::permanent(ErrorKind::Unexpected, "broken");
+
+    assert!(warning.emit());
+    assert_eq!(RECORDS.load(Ordering::SeqCst), 1);
+
+    set_min_log_severity(Severity::Error);
+    assert_eq!(min_log_severity(), Severity::Error);
+    assert!(!warning.emit());
+    assert_eq!(RECORDS.load(Ordering::SeqCst), 1);
+    assert!(error.emit());
+    assert_eq!(RECORDS.load(Ordering::SeqCst), 2);
+
+    set_min_log_severity(Severity::Info);
+}
@@ -0,0 +1,23 @@
+//! The context cap is process-wide, so it is exercised in its own test binary.
+
+use erract::error::{TRUNCATED_KEY, max_context};
+use erract::{Error, set_max_context};
+
+#[test]
+fn test_max_context_truncates() {
+    assert_eq!(max_context(), usize::MAX);
+    set_max_context(5);
+
+    let mut error = Error::not_found();
+    for i in 0..10 {
+        error = error.with_context(format!("key{i}"), format!("value{i}"));
+    }
+
+    let context = error.context();
+    assert_eq!(context.len(), 6);
+    assert_eq!(context[4].0, "key4");
+    assert_eq!(context[5].0, TRUNCATED_KEY);
+    assert_eq!(context[5].1, "true");
+
+    set_max_context(usize::MAX);
+}
@@ -0,0 +1,27 @@
+//! The value length cap is process-wide, so it is exercised in its own test binary.
+
+use erract::error::{TRUNCATED_VALUE_SUFFIX, max_context_value_len};
+use erract::{Error, set_max_context_value_len};
+
+#[test]
+fn test_max_context_value_len_truncates() {
+    assert_eq!(max_context_value_len(), usize::MAX);
+    set_max_context_value_len(8);
+
+    let body = "x".repeat(1 << 20);
+    let error = Error::not_found()
+        .with_context("body", body)
+        .with_context_value("accented", "ééééééééééé")
+        .with_context("short", "12345678");
+
+    let context = error.context();
+    assert_eq!(context[0].1, format!("xxxxxxxx{TRUNCATED_VALUE_SUFFIX}"));
+    assert_eq!(
+        context[0].1.chars().count(),
+        8 + TRUNCATED_VALUE_SUFFIX.chars().count()
+    );
+    assert_eq!(context[1].1, format!("éééééééé{TRUNCATED_VALUE_SUFFIX}"));
+    assert_eq!(context[2].1, "12345678");
+
+    set_max_context_value_len(usize::MAX);
+}
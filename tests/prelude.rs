@@ -0,0 +1,41 @@
+//! Checks that `use erract::prelude::*` is enough for everyday code.
+
+use erract::prelude::*;
+
+#[test]
+fn test_core_items() {
+    let policy = RetryPolicy::new(1);
+    let result: std::result::Result<(), Error> = retry_with(&policy, || Err(Error::not_found()));
+    let error = result.unwrap_err();
+    assert_eq!(error.kind(), &ErrorKind::NotFound);
+    assert_eq!(error.kind().severity(), Severity::Warning);
+
+    let exn = error.raise();
+    assert!(has_permanent(&exn));
+    assert!(!has_retryable(&exn));
+    assert_eq!(
+        root_cause(&exn).map(Error::kind),
+        Some(&ErrorKind::NotFound)
+    );
+}
+
+#[cfg(feature = "http")]
+#[test]
+fn test_http_kind() {
+    let kind = HttpErrorKind::ServerError(503);
+    assert!(Error::http(kind, "unavailable").is_retryable());
+}
+
+#[cfg(feature = "db")]
+#[test]
+fn test_database_kind() {
+    let error = Error::database(DatabaseErrorKind::Deadlock, "deadlock");
+    assert_eq!(error.status(), &ErrorStatus::Temporary);
+}
+
+#[cfg(feature = "storage")]
+#[test]
+fn test_storage_kind() {
+    let error = Error::storage(StorageErrorKind::NotFound, "missing");
+    assert!(error.is_permanent());
+}
@@ -0,0 +1,47 @@
+//! Environment variables are process-wide, so `RetryPolicy::from_env` is
+//! exercised in its own test binary, with a distinct prefix per test.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use erract::{Jitter, RetryPolicy};
+
+/// Serializes environment access between the tests of this binary.
+static ENV: Mutex<()> = Mutex::new(());
+
+#[test]
+fn test_from_env_reads_all_variables() {
+    let _guard = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    // SAFETY: the guard keeps other tests in this binary from touching the environment.
+    unsafe {
+        std::env::set_var("ENV_TEST_A_MAX_ATTEMPTS", "7");
+        std::env::set_var("ENV_TEST_A_BASE_DELAY_MS", "250");
+        std::env::set_var("ENV_TEST_A_MAX_DELAY_MS", "5000");
+        std::env::set_var("ENV_TEST_A_JITTER", "Full");
+    }
+
+    let policy = RetryPolicy::from_env("ENV_TEST_A");
+    assert_eq!(policy.max_attempts(), 7);
+    assert_eq!(policy.base_delay(), Duration::from_millis(250));
+    assert_eq!(policy.max_delay(), Duration::from_secs(5));
+    assert_eq!(policy.jitter(), Jitter::Full);
+}
+
+#[test]
+fn test_from_env_falls_back_on_invalid_values() {
+    let _guard = ENV.lock().unwrap_or_else(|e| e.into_inner());
+    // SAFETY: the guard keeps other tests in this binary from touching the environment.
+    unsafe {
+        std::env::set_var("ENV_TEST_B_MAX_ATTEMPTS", "many");
+        std::env::set_var("ENV_TEST_B_BASE_DELAY_MS", "-5");
+        std::env::set_var("ENV_TEST_B_JITTER", "sometimes");
+        std::env::set_var("ENV_TEST_B_MAX_DELAY_MS", " 1200 ");
+    }
+
+    let policy = RetryPolicy::from_env("ENV_TEST_B");
+    let default = RetryPolicy::default();
+    assert_eq!(policy.max_attempts(), default.max_attempts());
+    assert_eq!(policy.base_delay(), default.base_delay());
+    assert_eq!(policy.jitter(), default.jitter());
+    assert_eq!(policy.max_delay(), Duration::from_millis(1200));
+}
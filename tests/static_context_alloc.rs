@@ -0,0 +1,64 @@
+//! Counts allocations with a global allocator, so it runs in its own test binary.
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use erract::Error;
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|n| n.set(n.get() + 1));
+        unsafe { System.realloc(ptr, layout, new_size) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn count_allocations<T>(f: impl FnOnce() -> T) -> (T, usize) {
+    let before = ALLOCATIONS.with(Cell::get);
+    let value = f();
+    (value, ALLOCATIONS.with(Cell::get) - before)
+}
+
+#[test]
+fn test_with_static_context_allocates_only_storage() {
+    static FEW: &[(&str, &str)] = &[("component", "billing")];
+    static MANY: &[(&str, &str)] = &[
+        ("component", "billing"),
+        ("region", "eu"),
+        ("tier", "gold"),
+        ("shard", "7"),
+        ("zone", "b"),
+        ("rack", "12"),
+        ("pool", "main"),
+        ("role", "primary"),
+    ];
+
+    let (few, few_allocs) = count_allocations(|| Error::not_found().with_static_context(FEW));
+    let (many, many_allocs) = count_allocations(|| Error::not_found().with_static_context(MANY));
+
+    // A scratch Vec and the shared slice; no key or value strings.
+    assert_eq!(few_allocs, 2);
+    assert_eq!(many_allocs, few_allocs);
+    assert_eq!(few.context().len(), 1);
+    assert_eq!(many.context().len(), MANY.len());
+
+    let (_, empty_allocs) = count_allocations(|| Error::not_found().with_static_context(&[]));
+    assert_eq!(empty_allocs, 0);
+}
@@ -0,0 +1,19 @@
+//! Strict key checking is process-wide, so it is exercised in its own test binary.
+
+use erract::{Error, set_strict_context_keys};
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "empty context key")]
+fn test_strict_mode_rejects_empty_key() {
+    set_strict_context_keys(true);
+    let _ = Error::not_found().with_context("", "value");
+}
+
+#[cfg(not(debug_assertions))]
+#[test]
+fn test_strict_mode_skips_empty_key_in_release() {
+    set_strict_context_keys(true);
+    let error = Error::not_found().with_context("", "value");
+    assert!(error.context().is_empty());
+}